@@ -0,0 +1,444 @@
+use chrono::{DateTime, Utc};
+
+use crate::errors::{FileAttributeKind, FileLocation, ParseNzbError};
+use crate::file::File;
+use crate::meta::Meta;
+use crate::nzb::Nzb;
+use crate::parser::{validate_file, validate_files};
+use crate::segment::Segment;
+
+/// Builder for constructing a [`File`] that satisfies the same
+/// invariants the parser enforces on every `<file>` element.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::DateTime;
+/// use nzb_rs::{File, ParseNzbError, Segment};
+///
+/// fn main() -> Result<(), ParseNzbError> {
+///     let file = File::builder()
+///         .poster("John <nzb@nowhere.example>")
+///         .posted_at(DateTime::from_timestamp(1706440708, 0).unwrap())
+///         .subject(r#"[1/1] - "Big Buck Bunny - S01E01.mkv" yEnc (1/2) 1478616"#)
+///         .group("alt.binaries.boneless")
+///         .segment(Segment::new(739067, 1, "9cacde4c986547369becbf97003fb2c5-9483514693959@example"))
+///         .segment(Segment::new(739549, 2, "70a3a038ce324e618e2751e063d6a036-7285710986748@example"))
+///         .build()?;
+///     assert_eq!(file.name(), Some("Big Buck Bunny - S01E01.mkv"));
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileBuilder {
+    poster: Option<String>,
+    posted_at: Option<DateTime<Utc>>,
+    subject: Option<String>,
+    groups: Vec<String>,
+    segments: Vec<Segment>,
+}
+
+impl FileBuilder {
+    /// Creates a new, empty [`FileBuilder`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the poster of the file.
+    #[must_use]
+    pub fn poster(mut self, poster: impl Into<String>) -> Self {
+        self.poster = Some(poster.into());
+        self
+    }
+
+    /// Sets the date and time the file was posted.
+    #[must_use]
+    pub fn posted_at(mut self, posted_at: impl Into<DateTime<Utc>>) -> Self {
+        self.posted_at = Some(posted_at.into());
+        self
+    }
+
+    /// Sets the subject of the file.
+    #[must_use]
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Adds a Usenet group the file was posted to.
+    #[must_use]
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.groups.push(group.into());
+        self
+    }
+
+    /// Adds multiple Usenet groups the file was posted to.
+    #[must_use]
+    pub fn groups(mut self, groups: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.groups.extend(groups.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a segment to the file.
+    #[must_use]
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    /// Adds multiple segments to the file.
+    #[must_use]
+    pub fn segments(mut self, segments: impl IntoIterator<Item = Segment>) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    /// Builds the [`File`].
+    ///
+    /// Mirroring the parser, blank groups and segments with a blank `Message-ID`
+    /// are discarded, and the remaining groups and segments are sorted.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbError`] the parser would:
+    /// - [`ParseNzbError::FileAttribute`] if the poster, date, or subject was not set.
+    /// - [`ParseNzbError::GroupsElement`] if there is no non-blank group.
+    /// - [`ParseNzbError::SegmentsElement`] if there is no segment with a non-blank `Message-ID`.
+    pub fn build(self) -> Result<File, ParseNzbError> {
+        let missing = |kind| ParseNzbError::FileAttribute {
+            kind,
+            location: FileLocation::new(None, self.subject.as_deref(), None),
+        };
+
+        let poster = self.poster.clone().ok_or_else(|| missing(FileAttributeKind::Poster))?;
+        let posted_at = self.posted_at.ok_or_else(|| missing(FileAttributeKind::Date))?;
+        let subject = self
+            .subject
+            .clone()
+            .ok_or_else(|| missing(FileAttributeKind::Subject))?;
+
+        let mut file = File::new(poster, posted_at, subject, self.groups, self.segments);
+        file.discard_blank();
+        validate_file(file, None, None)
+    }
+}
+
+/// Builder for constructing an [`Nzb`] that satisfies the same
+/// invariants the parser enforces on an NZB document.
+///
+/// An existing [`Nzb`] can be edited by converting it into an [`NzbBuilder`] and building it again,
+/// which validates the result the same way.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::DateTime;
+/// use nzb_rs::{File, Meta, Nzb, ParseNzbError, Segment};
+///
+/// fn main() -> Result<(), ParseNzbError> {
+///     let file = File::builder()
+///         .poster("John <nzb@nowhere.example>")
+///         .posted_at(DateTime::from_timestamp(1706440708, 0).unwrap())
+///         .subject(r#"[1/1] - "Big Buck Bunny - S01E01.mkv" yEnc (1/1) 739067"#)
+///         .group("alt.binaries.boneless")
+///         .segment(Segment::new(739067, 1, "9cacde4c986547369becbf97003fb2c5-9483514693959@example"))
+///         .build()?;
+///
+///     let nzb = Nzb::builder()
+///         .meta(Meta::new(Some("Big Buck Bunny"), ["secret"], ["HD"], Some("TV")))
+///         .file(file)
+///         .build()?;
+///     assert_eq!(nzb.meta().title(), Some("Big Buck Bunny"));
+///     assert_eq!(nzb.file().name(), Some("Big Buck Bunny - S01E01.mkv"));
+///     Ok(())
+/// }
+/// ```
+///
+/// Stripping the passwords and extras from an existing NZB:
+///
+/// ```rust
+/// use nzb_rs::{Nzb, NzbBuilder, ParseNzbFileError};
+///
+/// fn main() -> Result<(), ParseNzbFileError> {
+///     let nzb = Nzb::parse_file("tests/nzbs/spec_example.nzb")?;
+///     let mut builder = NzbBuilder::from(nzb).retain_files(|file| !file.is_extra());
+///     builder.meta_mut().remove("password");
+///     let nzb = builder.build()?;
+///     assert!(nzb.meta().passwords().is_empty());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NzbBuilder {
+    meta: Meta,
+    files: Vec<File>,
+    source_name: Option<String>,
+}
+
+impl From<Nzb> for NzbBuilder {
+    /// Creates an [`NzbBuilder`] with the metadata and files of an existing [`Nzb`], to edit it.
+    ///
+    /// The built [`Nzb`] keeps the [`Nzb::source_name`] of the original.
+    fn from(nzb: Nzb) -> Self {
+        let (meta, files, source_name) = nzb.into_parts();
+        Self {
+            meta,
+            files,
+            source_name,
+        }
+    }
+}
+
+impl NzbBuilder {
+    /// Creates a new, empty [`NzbBuilder`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the metadata of the NZB.
+    #[must_use]
+    pub fn meta(mut self, meta: Meta) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    /// Adds a file to the NZB.
+    #[must_use]
+    pub fn file(mut self, file: File) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Adds multiple files to the NZB.
+    #[must_use]
+    pub fn files(mut self, files: impl IntoIterator<Item = File>) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    /// Keeps only the files for which `f` returns [`true`], in order.
+    #[must_use]
+    pub fn retain_files(mut self, f: impl FnMut(&File) -> bool) -> Self {
+        self.files.retain(f);
+        self
+    }
+
+    /// Metadata of the NZB, for editing it in place.
+    pub fn meta_mut(&mut self) -> &mut Meta {
+        &mut self.meta
+    }
+
+    /// Files added so far, for editing them in place, e.g., to add, remove, or rewrite files.
+    ///
+    /// The files are validated when the [`Nzb`] is built, so they may be left invalid in the meantime.
+    pub fn files_mut(&mut self) -> &mut Vec<File> {
+        &mut self.files
+    }
+
+    /// Builds the [`Nzb`].
+    ///
+    /// Files are validated and sorted the same way the parser does, see [`FileBuilder::build`],
+    /// so blank groups and segments with a blank `Message-ID` left by editing are discarded.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbError`] the parser would:
+    /// - [`ParseNzbError::GroupsElement`] if a file has no non-blank group.
+    /// - [`ParseNzbError::SegmentsElement`] if a file has no segment with a non-blank `Message-ID`.
+    /// - [`ParseNzbError::FileElement`] if no file was added.
+    /// - [`ParseNzbError::OnlyPar2Files`] if every file is a `.par2` file.
+    pub fn build(self) -> Result<Nzb, ParseNzbError> {
+        let files = self
+            .files
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut file)| {
+                file.discard_blank();
+                validate_file(file, Some(index), None)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let files = validate_files(files)?;
+        Ok(Nzb::new(self.meta, files, self.source_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_builder(subject: &str) -> FileBuilder {
+        FileBuilder::new()
+            .poster("Joe Bloggs <bloggs@nowhere.example>")
+            .posted_at(DateTime::from_timestamp(1071674882, 0).unwrap())
+            .subject(subject)
+            .group("alt.binaries.newzbin")
+            .segment(Segment::new(102394, 1, "123456789abcdef@news.newzbin.com"))
+    }
+
+    #[test]
+    fn test_file_builder_missing_attributes() {
+        let complete = file_builder("abc-mr2a.r01 (1/2)");
+
+        let location = FileLocation::new(None, Some("abc-mr2a.r01 (1/2)"), None);
+
+        let mut missing = complete.clone();
+        missing.poster = None;
+        assert_eq!(
+            missing.build(),
+            Err(ParseNzbError::FileAttribute {
+                kind: FileAttributeKind::Poster,
+                location: location.clone()
+            })
+        );
+
+        let mut missing = complete.clone();
+        missing.posted_at = None;
+        assert_eq!(
+            missing.build(),
+            Err(ParseNzbError::FileAttribute {
+                kind: FileAttributeKind::Date,
+                location
+            })
+        );
+
+        let mut missing = complete.clone();
+        missing.subject = None;
+        assert_eq!(
+            missing.build(),
+            Err(ParseNzbError::FileAttribute {
+                kind: FileAttributeKind::Subject,
+                location: FileLocation::default()
+            })
+        );
+
+        assert!(complete.build().is_ok());
+    }
+
+    #[test]
+    fn test_file_builder_discards_empty_groups_and_segments() {
+        let location = FileLocation::new(None, Some("abc-mr2a.r01 (1/2)"), None);
+
+        let mut builder = file_builder("abc-mr2a.r01 (1/2)");
+        builder.groups = vec![String::new(), " \t".to_string()];
+        assert_eq!(
+            builder.clone().build(),
+            Err(ParseNzbError::GroupsElement {
+                location: location.clone()
+            })
+        );
+
+        builder.groups = vec!["alt.binaries.newzbin".to_string()];
+        builder.segments = vec![Segment::new(102394, 1, ""), Segment::new(102394, 2, " ")];
+        assert_eq!(builder.build(), Err(ParseNzbError::SegmentsElement { location }));
+    }
+
+    #[test]
+    fn test_file_builder_sorts() {
+        let file = file_builder("abc-mr2a.r01 (1/2)")
+            .group("alt.binaries.mojo")
+            .segment(Segment::new(4501, 0, "987654321fedbca@news.newzbin.com"))
+            .build()
+            .unwrap();
+
+        assert_eq!(file.groups(), ["alt.binaries.mojo", "alt.binaries.newzbin"]);
+        assert_eq!(
+            file.segments().iter().map(Segment::number).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_nzb_builder_errors() {
+        assert_eq!(NzbBuilder::new().build(), Err(ParseNzbError::FileElement));
+
+        let par2 = file_builder(r#"[1/1] - "abc.par2" yEnc (1/1) 388"#).build().unwrap();
+        assert_eq!(NzbBuilder::new().file(par2).build(), Err(ParseNzbError::OnlyPar2Files));
+
+        let groupless = File::new(
+            "poster",
+            DateTime::from_timestamp(0, 0).unwrap(),
+            "abc.rar",
+            Vec::<String>::new(),
+            vec![Segment::new(1, 1, "id@example")],
+        );
+        assert_eq!(
+            NzbBuilder::new().file(groupless).build(),
+            Err(ParseNzbError::GroupsElement {
+                location: FileLocation::new(Some(0), Some("abc.rar"), None)
+            })
+        );
+
+        let segmentless = File::new(
+            "poster",
+            DateTime::from_timestamp(0, 0).unwrap(),
+            "abc.rar",
+            vec!["alt.binaries.test"],
+            Vec::new(),
+        );
+        let valid = file_builder("abc.r00").build().unwrap();
+        assert_eq!(
+            NzbBuilder::new().file(valid).file(segmentless).build(),
+            Err(ParseNzbError::SegmentsElement {
+                location: FileLocation::new(Some(1), Some("abc.rar"), None)
+            })
+        );
+    }
+
+    #[test]
+    fn test_nzb_builder_sorts_files() {
+        let nzb = NzbBuilder::new()
+            .files([
+                file_builder(r#"[10/10] - "b.rar" yEnc (1/1) 1"#).build().unwrap(),
+                file_builder(r#"[2/10] - "a.rar" yEnc (1/1) 1"#).build().unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            nzb.files().iter().map(File::subject).collect::<Vec<_>>(),
+            vec![r#"[2/10] - "a.rar" yEnc (1/1) 1"#, r#"[10/10] - "b.rar" yEnc (1/1) 1"#]
+        );
+    }
+
+    #[test]
+    fn test_nzb_builder_from_nzb() {
+        let nzb = NzbBuilder::new()
+            .meta(Meta::new(Some("Title"), ["secret"], ["HD"], None::<String>))
+            .files([
+                file_builder(r#"[1/3] - "abc.rar" yEnc (1/1) 1"#).build().unwrap(),
+                file_builder(r#"[2/3] - "abc.nfo" yEnc (1/1) 1"#).build().unwrap(),
+                file_builder(r#"[3/3] - "abc.par2" yEnc (1/1) 1"#).build().unwrap(),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(NzbBuilder::from(nzb.clone()).build(), Ok(nzb.clone()));
+
+        let mut builder = NzbBuilder::from(nzb.clone()).retain_files(|file| !file.is_extra());
+        builder.meta_mut().remove("password");
+        builder.files_mut()[0].set_subject(r#"[1/3] - "xyz.rar" yEnc (1/1) 1"#);
+        let edited = builder.build().unwrap();
+        assert_eq!(edited.meta().title(), Some("Title"));
+        assert!(edited.meta().passwords().is_empty());
+        assert_eq!(
+            edited.files().iter().filter_map(File::name).collect::<Vec<_>>(),
+            vec!["xyz.rar", "abc.par2"]
+        );
+
+        // The invariants are checked again.
+        let mut builder = NzbBuilder::from(nzb.clone()).retain_files(File::is_par2);
+        assert_eq!(builder.clone().build(), Err(ParseNzbError::OnlyPar2Files));
+        builder.files_mut().clear();
+        assert_eq!(builder.build(), Err(ParseNzbError::FileElement));
+
+        let mut builder = NzbBuilder::from(nzb);
+        builder.files_mut()[1].set_groups([""]);
+        assert_eq!(
+            builder.build(),
+            Err(ParseNzbError::GroupsElement {
+                location: FileLocation::new(Some(1), Some(r#"[2/3] - "abc.nfo" yEnc (1/1) 1"#), None)
+            })
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/// Represents the kinds of problems the parser can work around by skipping part of a 'file' element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticKind {
+    /// A 'segment' element whose 'bytes' attribute is missing or not a valid size.
+    SegmentBytes,
+    /// A 'segment' element whose 'number' attribute is missing or not a valid number.
+    SegmentNumber,
+    /// A 'segment' element with an empty or missing Message-ID.
+    SegmentMessageId,
+    /// A 'group' element with an empty or missing name.
+    EmptyGroup,
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SegmentBytes => write!(f, "invalid or missing 'bytes' attribute in a 'segment' element"),
+            Self::SegmentNumber => write!(f, "invalid or missing 'number' attribute in a 'segment' element"),
+            Self::SegmentMessageId => write!(f, "empty or missing Message-ID in a 'segment' element"),
+            Self::EmptyGroup => write!(f, "empty or missing name in a 'group' element"),
+        }
+    }
+}
+
+/// A problem found while parsing an NZB that did not prevent it from being parsed,
+/// but caused part of a 'file' element to be skipped.
+///
+/// Diagnostics are collected by [`Nzb::parse_lenient`](crate::Nzb::parse_lenient)
+/// and turned into errors by [`Nzb::parse_strict`](crate::Nzb::parse_strict).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    file_index: usize,
+    subject: String,
+    element_index: usize,
+    kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(
+        file_index: usize,
+        subject: impl Into<String>,
+        element_index: usize,
+        kind: DiagnosticKind,
+    ) -> Self {
+        Self {
+            file_index,
+            subject: subject.into(),
+            element_index,
+            kind,
+        }
+    }
+
+    /// Zero-based index of the 'file' element the problem was found in, in document order.
+    ///
+    /// Note that this is not necessarily the index of the file in [`Nzb::files`](crate::Nzb::files),
+    /// which are sorted after parsing. Use [`Diagnostic::subject`] to find the file there.
+    #[must_use]
+    pub fn file_index(&self) -> usize {
+        self.file_index
+    }
+
+    /// Subject of the 'file' element the problem was found in.
+    #[must_use]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Zero-based index of the skipped 'segment' or 'group' element
+    /// among its siblings, in document order.
+    #[must_use]
+    pub fn element_index(&self) -> usize {
+        self.element_index
+    }
+
+    /// What was wrong with the skipped element.
+    #[must_use]
+    pub fn kind(&self) -> DiagnosticKind {
+        self.kind
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let element = match self.kind {
+            DiagnosticKind::EmptyGroup => "group",
+            _ => "segment",
+        };
+        write!(
+            f,
+            "{} (file #{} '{}', {element} #{})",
+            self.kind, self.file_index, self.subject, self.element_index
+        )
+    }
+}
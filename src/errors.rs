@@ -2,6 +2,9 @@ use std::{io, path::PathBuf};
 
 use thiserror::Error;
 
+use crate::diagnostic::Diagnostic;
+use crate::input::{Codec, DecodeError, DecompressError};
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represents the attributes that can be present in a 'file' element of an NZB document.
 pub enum FileAttributeKind {
@@ -20,24 +23,107 @@ impl std::fmt::Display for FileAttributeKind {
     }
 }
 
+/// A position in the text of an NZB document.
+///
+/// Both the line and the column are one-based, and the column is counted in characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextPosition {
+    /// One-based line number.
+    pub line: u32,
+    /// One-based column number, counted in characters.
+    pub column: u32,
+}
+
+impl std::fmt::Display for TextPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Identifies the 'file' element of an NZB document an error occurred in.
+///
+/// Every part is optional, because not every source of a file knows all of them:
+/// - [`Nzb::parse`](crate::Nzb::parse) and friends, and [`NzbReader`](crate::NzbReader),
+///   know the index and position of every file.
+/// - [`NzbBuilder::build`](crate::NzbBuilder::build) knows the index in which files were added.
+/// - [`FileBuilder::build`](crate::FileBuilder::build) knows neither.
+///
+/// The subject is missing if the error is that the file has no subject.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileLocation {
+    index: Option<usize>,
+    subject: Option<String>,
+    position: Option<TextPosition>,
+}
+
+impl FileLocation {
+    pub(crate) fn new(index: Option<usize>, subject: Option<&str>, position: Option<TextPosition>) -> Self {
+        Self {
+            index,
+            subject: subject.map(str::to_string),
+            position,
+        }
+    }
+
+    /// Zero-based index of the 'file' element, in document order.
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Subject of the 'file' element.
+    #[must_use]
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+
+    /// Position of the start tag of the 'file' element.
+    #[must_use]
+    pub fn position(&self) -> Option<TextPosition> {
+        self.position
+    }
+}
+
+impl std::fmt::Display for FileLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'file' element")?;
+        if let Some(index) = self.index {
+            write!(f, " #{index}")?;
+        }
+        if let Some(subject) = &self.subject {
+            write!(f, " ('{subject}')")?;
+        }
+        if let Some(position) = self.position {
+            write!(f, " at {position}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Error, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Represents errors that can occur during the parsing of an NZB document.
 pub enum ParseNzbError {
     /// Indicates an invalid or missing 'groups' element within a 'file' element.
     /// Each 'file' element must contain at least one valid 'groups' element.
     #[error(
-        "Invalid or missing 'groups' element within a 'file' element. \
+        "Invalid or missing 'groups' element within {location}. \
         Each 'file' element must contain at least one valid 'groups' element."
     )]
-    GroupsElement,
+    GroupsElement {
+        /// The 'file' element the error occurred in.
+        location: FileLocation,
+    },
 
     /// Indicates an invalid or missing 'segments' element within a 'file' element.
     /// Each 'file' element must contain at least one valid 'segments' element.
     #[error(
-        "Invalid or missing 'segments' element within a 'file' element. \
+        "Invalid or missing 'segments' element within {location}. \
         Each 'file' element must contain at least one valid 'segments' element."
     )]
-    SegmentsElement,
+    SegmentsElement {
+        /// The 'file' element the error occurred in.
+        location: FileLocation,
+    },
 
     /// Indicates an invalid or missing 'file' element in the NZB document.
     /// The NZB document must contain at least one valid 'file' element.
@@ -56,25 +142,80 @@ pub enum ParseNzbError {
     OnlyPar2Files,
 
     /// Indicates an invalid or missing required attribute in a 'file' element.
-    #[error("Invalid or missing required attribute '{0}' in a 'file' element.")]
-    FileAttribute(FileAttributeKind),
+    #[error("Invalid or missing required attribute '{kind}' in {location}.")]
+    FileAttribute {
+        /// The attribute that is invalid or missing.
+        kind: FileAttributeKind,
+        /// The 'file' element the error occurred in.
+        location: FileLocation,
+    },
 
     /// Indicates that the NZB document is not valid XML and could not be parsed.
+    #[error(
+        "The NZB document is not valid XML and could not be parsed: {message}{}",
+        .position.map(|position| format!(" at {position}")).unwrap_or_default()
+    )]
+    XmlSyntax {
+        /// The error message provided by the underlying XML parsing library
+        /// ([`roxmltree`](https://crates.io/crates/roxmltree), or
+        /// [`quick-xml`](https://crates.io/crates/quick-xml) for [`NzbReader`](crate::NzbReader)).
+        message: String,
+        /// Where in the document the error occurred, if known.
+        /// This is [`None`] for errors that are not tied to a position, such as an unexpected end of the document.
+        position: Option<TextPosition>,
+    },
+
+    /// Indicates that the XML declaration of the NZB document names a character encoding that is not supported.
+    ///
+    /// The contained string is the declared encoding label. Supported labels are those of the
+    /// [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels).
+    #[error("The NZB document declares an unsupported character encoding: '{0}'")]
+    UnsupportedEncoding(String),
+
+    /// Indicates that the NZB document contains bytes that are not valid in its character encoding.
+    ///
+    /// The contained string is the name of the encoding, e.g., `UTF-8`, as detected from the
+    /// byte order mark or the XML declaration.
+    #[error("The NZB document is not valid {0}")]
+    InvalidEncoding(String),
+
+    /// Indicates that part of a 'file' element was invalid and would have been skipped.
     ///
-    /// The contained string is the error message provided by the underlying
-    /// XML parsing library ([`roxmltree`](https://crates.io/crates/roxmltree) in this case).
-    #[error("The NZB document is not valid XML and could not be parsed: {0}")]
-    XmlSyntax(String),
+    /// This is only returned by [`Nzb::parse_strict`](crate::Nzb::parse_strict),
+    /// which rejects what [`Nzb::parse_lenient`](crate::Nzb::parse_lenient) reports as a [`Diagnostic`].
+    #[error("Invalid element in the NZB document: {0}")]
+    Diagnostic(Diagnostic),
 }
 
-impl From<roxmltree::Error> for ParseNzbError {
-    fn from(error: roxmltree::Error) -> Self {
-        ParseNzbError::XmlSyntax(error.to_string())
+impl ParseNzbError {
+    pub(crate) fn xml_syntax(message: impl Into<String>, position: Option<TextPosition>) -> Self {
+        ParseNzbError::XmlSyntax {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl From<DecodeError> for ParseNzbError {
+    fn from(error: DecodeError) -> Self {
+        match error {
+            DecodeError::Malformed(encoding) => ParseNzbError::InvalidEncoding(encoding.to_owned()),
+            DecodeError::Unsupported(label) => ParseNzbError::UnsupportedEncoding(label),
+        }
+    }
+}
+
+impl From<quick_xml::Error> for ParseNzbError {
+    fn from(error: quick_xml::Error) -> Self {
+        ParseNzbError::xml_syntax(error.to_string(), None)
     }
 }
 
 #[derive(Error, Debug)]
 /// Represents errors that can occur when attempting to parse an NZB file from a file path.
+///
+/// The enum is non-exhaustive, since the decompression variants depend on the enabled cargo features.
+#[non_exhaustive]
 pub enum ParseNzbFileError {
     /// Input/Output error encountered while trying to read the NZB file.
     #[error("I/O error while reading file '{file}': {source}")]
@@ -94,6 +235,58 @@ pub enum ParseNzbFileError {
         file: PathBuf,
     },
 
+    /// Error during Bzip2 decompression of the NZB file.
+    #[cfg(feature = "bzip2")]
+    #[error("Bzip2 decompression error for file '{file}': {source}")]
+    Bzip2 {
+        /// The underlying I/O error reported by the Bzip2 decompression process.
+        source: io::Error,
+        /// The path to the file that was being decompressed when the error occurred.
+        file: PathBuf,
+    },
+
+    /// Error during Xz decompression of the NZB file.
+    #[cfg(feature = "xz")]
+    #[error("Xz decompression error for file '{file}': {source}")]
+    Xz {
+        /// The underlying I/O error reported by the Xz decompression process.
+        source: io::Error,
+        /// The path to the file that was being decompressed when the error occurred.
+        file: PathBuf,
+    },
+
+    /// Error during Zstandard decompression of the NZB file.
+    #[cfg(feature = "zstd")]
+    #[error("Zstandard decompression error for file '{file}': {source}")]
+    Zstd {
+        /// The underlying I/O error reported by the Zstandard decompression process.
+        source: io::Error,
+        /// The path to the file that was being decompressed when the error occurred.
+        file: PathBuf,
+    },
+
+    /// Error while reading the zip archive itself.
+    #[cfg(feature = "zip")]
+    #[error("Zip archive error for file '{file}': {source}")]
+    Zip {
+        /// The underlying error reported by the zip archive reader.
+        source: zip::result::ZipError,
+        /// The path to the zip archive that was being read when the error occurred.
+        file: PathBuf,
+    },
+
+    /// Error while parsing one of the NZBs inside a zip archive.
+    #[cfg(feature = "zip")]
+    #[error("Error in entry '{entry}' of zip archive '{file}': {source}")]
+    ZipEntry {
+        /// The error encountered while parsing the entry.
+        source: ParseNzbReaderError,
+        /// The name of the entry within the zip archive.
+        entry: String,
+        /// The path to the zip archive that was being read when the error occurred.
+        file: PathBuf,
+    },
+
     ///  Error encountered during the core NZB parsing logic.
     #[error("NZB parsing error: {source}")]
     Parse {
@@ -110,10 +303,40 @@ impl ParseNzbFileError {
         }
     }
 
-    pub(crate) fn from_gzip_err(source: io::Error, file: impl Into<PathBuf>) -> Self {
-        ParseNzbFileError::Gzip {
-            source,
-            file: file.into(),
+    pub(crate) fn from_decompress_err(error: DecompressError, file: impl Into<PathBuf>) -> Self {
+        let DecompressError { codec, source } = error;
+        let file = file.into();
+        match codec {
+            Codec::Gzip => ParseNzbFileError::Gzip { source, file },
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2 => ParseNzbFileError::Bzip2 { source, file },
+            #[cfg(feature = "xz")]
+            Codec::Xz => ParseNzbFileError::Xz { source, file },
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => ParseNzbFileError::Zstd { source, file },
+        }
+    }
+
+    /// Attaches the path of the file that was being read to an error returned by a reader-based constructor.
+    #[cfg(feature = "zip")]
+    pub(crate) fn from_reader_err(error: ParseNzbReaderError, file: impl Into<PathBuf>) -> Self {
+        let file = file.into();
+        match error {
+            ParseNzbReaderError::Io { source } => ParseNzbFileError::Io { source, file },
+            ParseNzbReaderError::Gzip { source } => ParseNzbFileError::Gzip { source, file },
+            #[cfg(feature = "bzip2")]
+            ParseNzbReaderError::Bzip2 { source } => ParseNzbFileError::Bzip2 { source, file },
+            #[cfg(feature = "xz")]
+            ParseNzbReaderError::Xz { source } => ParseNzbFileError::Xz { source, file },
+            #[cfg(feature = "zstd")]
+            ParseNzbReaderError::Zstd { source } => ParseNzbFileError::Zstd { source, file },
+            ParseNzbReaderError::Zip { source } => ParseNzbFileError::Zip { source, file },
+            ParseNzbReaderError::ZipEntry { source, entry } => ParseNzbFileError::ZipEntry {
+                source: *source,
+                entry,
+                file,
+            },
+            ParseNzbReaderError::Parse { source } => ParseNzbFileError::Parse { source },
         }
     }
 }
@@ -123,3 +346,151 @@ impl From<ParseNzbError> for ParseNzbFileError {
         ParseNzbFileError::Parse { source }
     }
 }
+
+impl From<DecodeError> for ParseNzbFileError {
+    fn from(error: DecodeError) -> Self {
+        ParseNzbError::from(error).into()
+    }
+}
+
+#[derive(Error, Debug)]
+/// Represents errors that can occur when attempting to parse an NZB from a reader or a byte slice.
+///
+/// The enum is non-exhaustive, since the decompression variants depend on the enabled cargo features.
+#[non_exhaustive]
+pub enum ParseNzbReaderError {
+    /// Input/Output error encountered while trying to read the NZB.
+    #[error("I/O error while reading NZB: {source}")]
+    Io {
+        /// The underlying I/O error that occurred.
+        source: io::Error,
+    },
+
+    /// Error during Gzip decompression of the NZB.
+    #[error("Gzip decompression error: {source}")]
+    Gzip {
+        /// The underlying I/O error reported by the Gzip decompression process.
+        source: io::Error,
+    },
+
+    /// Error during Bzip2 decompression of the NZB.
+    #[cfg(feature = "bzip2")]
+    #[error("Bzip2 decompression error: {source}")]
+    Bzip2 {
+        /// The underlying I/O error reported by the Bzip2 decompression process.
+        source: io::Error,
+    },
+
+    /// Error during Xz decompression of the NZB.
+    #[cfg(feature = "xz")]
+    #[error("Xz decompression error: {source}")]
+    Xz {
+        /// The underlying I/O error reported by the Xz decompression process.
+        source: io::Error,
+    },
+
+    /// Error during Zstandard decompression of the NZB.
+    #[cfg(feature = "zstd")]
+    #[error("Zstandard decompression error: {source}")]
+    Zstd {
+        /// The underlying I/O error reported by the Zstandard decompression process.
+        source: io::Error,
+    },
+
+    /// Error while reading the zip archive itself.
+    #[cfg(feature = "zip")]
+    #[error("Zip archive error: {source}")]
+    Zip {
+        /// The underlying error reported by the zip archive reader.
+        source: zip::result::ZipError,
+    },
+
+    /// Error while parsing one of the NZBs inside a zip archive.
+    #[cfg(feature = "zip")]
+    #[error("Error in zip entry '{entry}': {source}")]
+    ZipEntry {
+        /// The error encountered while parsing the entry.
+        source: Box<ParseNzbReaderError>,
+        /// The name of the entry within the zip archive.
+        entry: String,
+    },
+
+    ///  Error encountered during the core NZB parsing logic.
+    #[error("NZB parsing error: {source}")]
+    Parse {
+        /// The specific NZB parsing error.
+        source: ParseNzbError,
+    },
+}
+
+impl From<ParseNzbError> for ParseNzbReaderError {
+    fn from(source: ParseNzbError) -> Self {
+        ParseNzbReaderError::Parse { source }
+    }
+}
+
+impl From<DecompressError> for ParseNzbReaderError {
+    fn from(error: DecompressError) -> Self {
+        let DecompressError { codec, source } = error;
+        match codec {
+            Codec::Gzip => ParseNzbReaderError::Gzip { source },
+            #[cfg(feature = "bzip2")]
+            Codec::Bzip2 => ParseNzbReaderError::Bzip2 { source },
+            #[cfg(feature = "xz")]
+            Codec::Xz => ParseNzbReaderError::Xz { source },
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => ParseNzbReaderError::Zstd { source },
+        }
+    }
+}
+
+#[cfg(feature = "zip")]
+impl From<zip::result::ZipError> for ParseNzbReaderError {
+    fn from(source: zip::result::ZipError) -> Self {
+        ParseNzbReaderError::Zip { source }
+    }
+}
+
+impl From<DecodeError> for ParseNzbReaderError {
+    fn from(error: DecodeError) -> Self {
+        ParseNzbError::from(error).into()
+    }
+}
+
+#[derive(Error, Debug)]
+/// Represents errors that can occur when attempting to write an NZB to a file path.
+pub enum WriteNzbFileError {
+    /// Input/Output error encountered while trying to write the NZB file.
+    #[error("I/O error while writing file '{file}': {source}")]
+    Io {
+        /// The underlying I/O error that occurred.
+        source: io::Error,
+        /// The path to the file that was being written when the error occurred.
+        file: PathBuf,
+    },
+
+    /// Error during Gzip compression of the NZB file.
+    #[error("Gzip compression error for file '{file}': {source}")]
+    Gzip {
+        /// The underlying I/O error reported by the Gzip compression process.
+        source: io::Error,
+        /// The path to the file that was being compressed when the error occurred.
+        file: PathBuf,
+    },
+}
+
+impl WriteNzbFileError {
+    pub(crate) fn from_io_err(source: io::Error, file: impl Into<PathBuf>) -> Self {
+        WriteNzbFileError::Io {
+            source,
+            file: file.into(),
+        }
+    }
+
+    pub(crate) fn from_gzip_err(source: io::Error, file: impl Into<PathBuf>) -> Self {
+        WriteNzbFileError::Gzip {
+            source,
+            file: file.into(),
+        }
+    }
+}
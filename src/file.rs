@@ -1,7 +1,17 @@
+use std::collections::{BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+
 use chrono::{DateTime, Utc};
 
+use crate::builder::FileBuilder;
+use crate::kind::FileKind;
+use crate::obfuscation::{DEFAULT_DETECTOR, ObfuscationAnalysis};
+use crate::par2::Par2Volume;
+use crate::rar::RarVolume;
+#[cfg(feature = "release")]
+use crate::release::ReleaseInfo;
 use crate::segment::Segment;
-use crate::subject;
+use crate::subject::{self, Subject};
 
 /// Represents a single file, consisting of segments that make up a file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -33,6 +43,51 @@ impl File {
         }
     }
 
+    /// Returns a [`FileBuilder`] for constructing a [`File`] that is
+    /// guaranteed to satisfy the same invariants as a parsed one.
+    #[must_use]
+    pub fn builder() -> FileBuilder {
+        FileBuilder::new()
+    }
+
+    /// Discards the groups with a blank name and the segments with a blank Message-ID from a file
+    /// that was built or edited by hand, since empty ones would not be read back from the written NZB.
+    pub(crate) fn discard_blank(&mut self) {
+        self.groups.retain(|group| !group.trim().is_empty());
+        self.segments.retain(|segment| !segment.message_id().trim().is_empty());
+    }
+
+    /// Sorts the groups alphabetically and the segments by their number.
+    pub(crate) fn sort(&mut self) {
+        self.groups.sort_unstable();
+        self.segments.sort_unstable_by_key(Segment::number);
+    }
+
+    /// Adds the groups and segments of `other` that the file does not have yet, telling segments apart
+    /// by their Message-ID, and returns the number of segments that were already present.
+    pub(crate) fn merge(&mut self, other: File) -> usize {
+        let mut duplicates = 0;
+        for group in other.groups {
+            if !self.groups.contains(&group) {
+                self.groups.push(group);
+            }
+        }
+        let mut message_ids = self
+            .segments
+            .iter()
+            .map(|segment| segment.message_id().to_owned())
+            .collect::<HashSet<_>>();
+        for segment in other.segments {
+            if message_ids.insert(segment.message_id().to_owned()) {
+                self.segments.push(segment);
+            } else {
+                duplicates += 1;
+            }
+        }
+        self.sort();
+        duplicates
+    }
+
     /// Poster of the file.
     #[must_use]
     pub fn poster(&self) -> &str {
@@ -63,6 +118,127 @@ impl File {
         &self.segments
     }
 
+    /// Replaces the subject of the file, e.g., to rename it.
+    pub fn set_subject(&mut self, subject: impl Into<String>) {
+        self.subject = subject.into();
+    }
+
+    /// Replaces the Usenet groups listed for the file.
+    pub fn set_groups(&mut self, groups: impl IntoIterator<Item = impl Into<String>>) {
+        self.groups = groups.into_iter().map(Into::into).collect();
+    }
+
+    /// Components of the subject, such as the file counter and the declared size.
+    #[must_use]
+    pub fn parsed_subject(&self) -> Subject<'_> {
+        Subject::parse(&self.subject)
+    }
+
+    /// Number of segments the file should have, according to the yEnc part counter in the subject,
+    /// e.g., `24` for `"Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056`.
+    /// May return [`None`] if the subject has no such counter.
+    #[must_use]
+    pub fn expected_segment_count(&self) -> Option<u32> {
+        self.parsed_subject()
+            .segment_counter()
+            .map(|counter| counter.total)
+            .filter(|&total| total > 0)
+    }
+
+    /// Ranges of segment numbers that are absent from the file, in ascending order,
+    /// e.g., `[3..=3, 7..=9]` if segments `3`, `7`, `8` and `9` are missing.
+    ///
+    /// Segments are numbered from `1` up to [`File::expected_segment_count`],
+    /// or, if the subject has no part counter, up to the highest segment number present.
+    /// There is at most one range more than there are segments, no matter how large the counter is.
+    #[must_use]
+    pub fn missing_segments(&self) -> Vec<RangeInclusive<u32>> {
+        let numbers = self.segments.iter().map(Segment::number).collect::<BTreeSet<_>>();
+        let last = self
+            .expected_segment_count()
+            .or_else(|| numbers.last().copied())
+            .unwrap_or_default();
+
+        let mut missing = Vec::new();
+        let mut next = 1;
+        for &number in numbers.range(1..=last) {
+            if number > next {
+                missing.push(next..=number - 1);
+            }
+            // The highest possible number leaves nothing after it to be missing.
+            let Some(after) = number.checked_add(1) else {
+                return missing;
+            };
+            next = after;
+        }
+        if next <= last {
+            missing.push(next..=last);
+        }
+        missing
+    }
+
+    /// Number of segments that are absent from the file, see [`File::missing_segments`].
+    #[must_use]
+    pub fn missing_segment_count(&self) -> u64 {
+        self.missing_segments()
+            .into_iter()
+            .map(|range| u64::from(range.end() - range.start()) + 1)
+            .sum()
+    }
+
+    /// Segment numbers that appear more than once in the file, in ascending order.
+    ///
+    /// Each number is only listed once, regardless of how often it is repeated.
+    #[must_use]
+    pub fn duplicate_segments(&self) -> Vec<u32> {
+        let mut seen = BTreeSet::new();
+        let duplicates = self
+            .segments
+            .iter()
+            .map(Segment::number)
+            .filter(|&number| !seen.insert(number))
+            .collect::<BTreeSet<_>>();
+        duplicates.into_iter().collect()
+    }
+
+    /// Return [`true`] if no segment is missing from the file, [`false`] otherwise.
+    ///
+    /// See [`File::missing_segments`] for how missing segments are determined.
+    /// Duplicate segments do not make a file incomplete.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        let Some(last) = self
+            .expected_segment_count()
+            .or_else(|| self.segments.iter().map(Segment::number).max())
+        else {
+            return true;
+        };
+        // Without enough segments to cover every number, there is no need to look any closer.
+        if (self.segments.len() as u64) < u64::from(last) {
+            return false;
+        }
+
+        let in_range = |number: &u32| (1..=last).contains(number);
+        let distinct = if self.segments.is_sorted_by_key(Segment::number) {
+            // Parsed and built files are sorted, so the numbers can be counted without allocating.
+            let mut previous = None;
+            self.segments
+                .iter()
+                .map(Segment::number)
+                .filter(in_range)
+                .filter(|&number| previous.replace(number) != Some(number))
+                .count()
+        } else {
+            self.segments
+                .iter()
+                .map(Segment::number)
+                .filter(in_range)
+                .collect::<BTreeSet<_>>()
+                .len()
+        };
+        distinct as u64 == u64::from(last)
+    }
+
     /// Size of the file calculated from the sum of segment sizes.
     #[must_use]
     pub fn size(&self) -> u64 {
@@ -100,20 +276,87 @@ impl File {
             .is_some_and(|file_ext| file_ext.eq_ignore_ascii_case(ext))
     }
 
+    /// Kind of the file, e.g., [`FileKind::Video`] for `movie.mkv`, as indicated by its [`File::name`].
+    /// Returns [`FileKind::Other`] if the name cannot be extracted.
+    #[must_use]
+    pub fn kind(&self) -> FileKind {
+        self.name().map_or(FileKind::Other, FileKind::from_name)
+    }
+
     /// Return [`true`] if the file is a `.par2` file, [`false`] otherwise.
     #[must_use]
     pub fn is_par2(&self) -> bool {
         self.name().is_some_and(subject::is_par2)
     }
 
+    /// Role of the file in its PAR2 recovery set, e.g., [`Par2Volume::Recovery`] for `movie.vol03+04.par2`.
+    /// Returns [`None`] if the file is not a `.par2` file.
+    #[must_use]
+    pub fn par2_volume(&self) -> Option<Par2Volume> {
+        self.name().and_then(Par2Volume::parse)
+    }
+
     /// Return [`true`] if the file is a `.rar` file, [`false`] otherwise.
     #[must_use]
     pub fn is_rar(&self) -> bool {
         self.name().is_some_and(subject::is_rar)
     }
 
+    /// Position of the file in a multi-volume RAR archive, e.g., volume `1` of `movie` for `movie.part02.rar`.
+    /// Returns [`None`] if the file is not a `.rar` file.
+    #[must_use]
+    pub fn rar_volume(&self) -> Option<RarVolume<'_>> {
+        self.name().and_then(RarVolume::parse)
+    }
+
+    /// Return [`true`] if the file is a sample of the release, e.g., `movie-sample.mkv`, [`false`] otherwise.
+    ///
+    /// This is a heuristic based on the word `sample` appearing in the [`File::name`].
+    #[must_use]
+    pub fn is_sample(&self) -> bool {
+        self.name().is_some_and(subject::is_sample)
+    }
+
+    /// Return [`true`] if the file is a proof of the release, e.g., `movie-proof.jpg`, [`false`] otherwise.
+    ///
+    /// This is a heuristic based on the word `proof` appearing in the [`File::name`].
+    #[must_use]
+    pub fn is_proof(&self) -> bool {
+        self.name().is_some_and(subject::is_proof)
+    }
+
+    /// Return [`true`] if the file is irrelevant to the main content of the release, [`false`] otherwise.
+    ///
+    /// This is a heuristic that covers `.nfo` and `.sfv` files, files in a `Subs/` folder, and
+    /// bonus material with words such as `extras`, `featurette` or `trailer` in the [`File::name`].
+    /// Samples and proofs are covered by [`File::is_sample`] and [`File::is_proof`] instead.
+    #[must_use]
+    pub fn is_extra(&self) -> bool {
+        matches!(self.kind(), FileKind::Nfo | FileKind::Sfv) || self.name().is_some_and(subject::is_extra)
+    }
+
+    /// Release information parsed from the [`File::stem`], see [`ReleaseInfo`].
+    /// May return [`None`] if it fails to extract the name.
+    #[cfg(feature = "release")]
+    #[must_use]
+    pub fn release_info(&self) -> Option<ReleaseInfo> {
+        self.stem().map(ReleaseInfo::parse)
+    }
+
     /// Return [`true`] if the file is obfuscated, [`false`] otherwise.
+    ///
+    /// See [`File::obfuscation`] for the reason behind the decision.
     pub fn is_obfuscated(&self) -> bool {
         self.stem().is_none_or(subject::is_obfuscated)
     }
+
+    /// Analysis of whether the file is obfuscated using the default [`ObfuscationDetector`],
+    /// reporting the rule that decided it. Use [`ObfuscationDetector::analyze_file`] to tune the rules.
+    ///
+    /// [`ObfuscationDetector`]: crate::ObfuscationDetector
+    /// [`ObfuscationDetector::analyze_file`]: crate::ObfuscationDetector::analyze_file
+    #[must_use]
+    pub fn obfuscation(&self) -> ObfuscationAnalysis {
+        DEFAULT_DETECTOR.analyze_file(self)
+    }
 }
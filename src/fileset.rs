@@ -0,0 +1,217 @@
+use std::collections::BTreeSet;
+
+use crate::file::File;
+use crate::par2::Par2Volume;
+
+/// A set of files in an NZB that belong together, such as the volumes of a
+/// RAR archive, along with the `.par2` files that can be used to repair them.
+///
+/// Files are grouped by the name they share once volume and `.par2` suffixes are stripped,
+/// so `movie.part01.rar`, `movie.part02.rar`, `movie.par2` and `movie.vol00+01.par2`
+/// form a single set named `movie`, and `movie.mkv` and `movie.mkv.par2` one named `movie.mkv`.
+///
+/// See [`Nzb::file_sets`](crate::Nzb::file_sets).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileSet<'a> {
+    name: &'a str,
+    files: Vec<&'a File>,
+    par2_files: Vec<&'a File>,
+}
+
+impl<'a> FileSet<'a> {
+    pub(crate) fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            files: Vec::new(),
+            par2_files: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, file: &'a File) {
+        if file.is_par2() {
+            self.par2_files.push(file);
+        } else {
+            self.files.push(file);
+        }
+    }
+
+    /// Name shared by the files in the set, e.g., `movie` for `movie.part01.rar`.
+    ///
+    /// For a file whose name cannot be extracted, this is its subject instead.
+    #[must_use]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Data files in the set, i.e., every file that is not a `.par2` file.
+    #[must_use]
+    pub fn files(&self) -> &[&'a File] {
+        &self.files
+    }
+
+    /// `.par2` files in the set.
+    #[must_use]
+    pub fn par2_files(&self) -> &[&'a File] {
+        &self.par2_files
+    }
+
+    /// Total size of the data files in the set.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|f| f.size()).sum()
+    }
+
+    /// Total size of the `.par2` files in the set.
+    #[must_use]
+    pub fn par2_size(&self) -> u64 {
+        self.par2_files.iter().map(|f| f.size()).sum()
+    }
+
+    /// Return [`true`] if there's at least one `.par2` file in the set, [`false`] otherwise.
+    #[must_use]
+    pub fn has_par2(&self) -> bool {
+        !self.par2_files.is_empty()
+    }
+
+    /// RAR volumes in the set in extraction order, see [`File::rar_volume`].
+    #[must_use]
+    pub fn rar_volumes(&self) -> Vec<&'a File> {
+        let mut volumes = self.files.iter().copied().filter(|f| f.is_rar()).collect::<Vec<_>>();
+        volumes.sort_by_key(|f| f.rar_volume().map(|volume| volume.number()));
+        volumes
+    }
+
+    /// Zero-based numbers of the RAR volumes missing from the set, in ascending order.
+    ///
+    /// Only gaps before the last volume present can be detected, since
+    /// volume names do not indicate how many volumes there are in total.
+    /// At most the first 10,000 missing numbers are listed, since a name
+    /// such as `movie.part4000000000.rar` would otherwise leave billions of gaps.
+    #[must_use]
+    pub fn missing_rar_volumes(&self) -> Vec<u32> {
+        const LIMIT: usize = 10_000;
+
+        let numbers = self
+            .files
+            .iter()
+            .filter_map(|f| f.rar_volume())
+            .map(|volume| volume.number())
+            .collect::<BTreeSet<_>>();
+
+        let mut missing = Vec::new();
+        let mut next = 0;
+        for number in numbers {
+            missing.extend((next..number).take(LIMIT - missing.len()));
+            if missing.len() == LIMIT {
+                break;
+            }
+            next = number.saturating_add(1);
+        }
+        missing
+    }
+
+    /// The `.par2` file to download first, which describes the files in the set.
+    ///
+    /// This is the index file, e.g., `movie.par2`, if there is one. Otherwise, it is the smallest
+    /// recovery volume, since every volume also describes the files it protects.
+    #[must_use]
+    pub fn par2_index(&self) -> Option<&'a File> {
+        self.par2_files
+            .iter()
+            .find(|f| f.par2_volume().is_some_and(|volume| volume.is_index()))
+            .or_else(|| self.par2_files.iter().min_by_key(|f| f.size()))
+            .copied()
+    }
+
+    /// Recovery volumes in the set, along with the number of recovery blocks in each.
+    fn recovery_volumes(&self) -> impl Iterator<Item = (&'a File, u32)> {
+        self.par2_files.iter().filter_map(|f| match f.par2_volume()? {
+            Par2Volume::Recovery { block_count, .. } if block_count > 0 => Some((*f, block_count)),
+            _ => None,
+        })
+    }
+
+    /// Total number of recovery blocks available in the set, as indicated by the names of its
+    /// recovery volumes, e.g., `3` for `movie.vol00+01.par2` and `movie.vol01+02.par2`.
+    #[must_use]
+    pub fn recovery_blocks(&self) -> u32 {
+        self.recovery_volumes()
+            .fold(0, |total: u32, (_, blocks)| total.saturating_add(blocks))
+    }
+
+    /// Estimated size of a PAR2 block in bytes, derived from the sizes of the recovery volumes.
+    /// Returns [`None`] if the set has no recovery volumes.
+    ///
+    /// Since segment sizes include the yEnc encoding overhead, this is a few percent larger
+    /// than the actual block size.
+    #[must_use]
+    pub fn block_size(&self) -> Option<u64> {
+        let (size, blocks) = self.recovery_volumes().fold((0, 0), |(size, blocks), (f, count)| {
+            (size + f.size(), blocks + u64::from(count))
+        });
+        (blocks > 0).then(|| size / blocks).filter(|&size| size > 0)
+    }
+
+    /// Estimated number of recovery blocks needed to repair the segments
+    /// missing from the data files in the set, see [`File::missing_segment_count`].
+    ///
+    /// This is an upper bound, since each missing segment is assumed to damage every block it overlaps.
+    /// Returns [`None`] if segments are missing, but the block size is unknown, see [`FileSet::block_size`].
+    #[must_use]
+    pub fn blocks_needed(&self) -> Option<u32> {
+        let block_size = self.block_size();
+        let mut needed: u64 = 0;
+
+        for file in &self.files {
+            let missing = file.missing_segment_count();
+            if missing == 0 {
+                continue;
+            }
+            let block_size = block_size?;
+            let segment_size = file
+                .size()
+                .checked_div(file.segments().len() as u64)
+                .unwrap_or(block_size);
+            let per_segment = segment_size.div_ceil(block_size) + 1;
+            let per_file = file
+                .size()
+                .saturating_add(missing.saturating_mul(segment_size))
+                .div_ceil(block_size);
+            needed = needed.saturating_add(missing.saturating_mul(per_segment).min(per_file));
+        }
+
+        Some(u32::try_from(needed).unwrap_or(u32::MAX))
+    }
+
+    /// Selects recovery volumes that together hold at least `blocks` recovery blocks,
+    /// preferring the combination that downloads the fewest blocks.
+    ///
+    /// Volumes are picked largest first among those that do not exceed the remaining need,
+    /// which is exact for the usual power-of-two volume sizes. If none fits, the smallest volume
+    /// that covers the rest is picked. Returns [`None`] if the set does not hold enough blocks.
+    #[must_use]
+    pub fn select_recovery_volumes(&self, blocks: u32) -> Option<Vec<&'a File>> {
+        let mut available = self.recovery_volumes().collect::<Vec<_>>();
+        if available.iter().map(|(_, count)| u64::from(*count)).sum::<u64>() < u64::from(blocks) {
+            return None;
+        }
+
+        let mut selected = Vec::new();
+        let mut remaining = blocks;
+
+        while remaining > 0 {
+            let index = available
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, count))| *count <= remaining)
+                .max_by_key(|(_, (_, count))| *count)
+                .or_else(|| available.iter().enumerate().min_by_key(|(_, (_, count))| *count))
+                .map(|(index, _)| index)?;
+            let (file, count) = available.swap_remove(index);
+            selected.push(file);
+            remaining = remaining.saturating_sub(count);
+        }
+
+        Some(selected)
+    }
+}
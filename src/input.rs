@@ -0,0 +1,362 @@
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::io::{self, Read};
+
+use encoding_rs::{Encoding, REPLACEMENT, UTF_8, UTF_16BE, UTF_16LE};
+use flate2::read::GzDecoder;
+
+/// A compression format an NZB can be wrapped in.
+///
+/// Gzip is always available, the others require their respective cargo feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Codec {
+    Gzip,
+    #[cfg(feature = "bzip2")]
+    Bzip2,
+    #[cfg(feature = "xz")]
+    Xz,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl Codec {
+    /// Every codec enabled in this build.
+    const ALL: &[Self] = &[
+        Self::Gzip,
+        #[cfg(feature = "bzip2")]
+        Self::Bzip2,
+        #[cfg(feature = "xz")]
+        Self::Xz,
+        #[cfg(feature = "zstd")]
+        Self::Zstd,
+    ];
+
+    /// Magic bytes at the start of every stream in this format.
+    ///
+    /// - Gzip: [RFC 1952, section 2.3.1](https://www.rfc-editor.org/rfc/rfc1952#section-2.3.1)
+    /// - Bzip2: `BZh`, followed by the block size
+    /// - Xz: [The .xz File Format, section 2.1.1.1](https://tukaani.org/xz/xz-file-format.txt)
+    /// - Zstandard: [RFC 8878, section 3.1.1](https://www.rfc-editor.org/rfc/rfc8878#section-3.1.1)
+    const fn magic(self) -> &'static [u8] {
+        match self {
+            Self::Gzip => &[0x1f, 0x8b],
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => b"BZh",
+            #[cfg(feature = "xz")]
+            Self::Xz => &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00],
+            #[cfg(feature = "zstd")]
+            Self::Zstd => &[0x28, 0xb5, 0x2f, 0xfd],
+        }
+    }
+
+    /// The file extension conventionally used for this format, without the leading dot.
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => "bz2",
+            #[cfg(feature = "xz")]
+            Self::Xz => "xz",
+            #[cfg(feature = "zstd")]
+            Self::Zstd => "zst",
+        }
+    }
+
+    /// Returns the codec whose magic bytes the input starts with, if any.
+    pub(crate) fn sniff(bytes: &[u8]) -> Option<Self> {
+        Self::ALL.iter().copied().find(|codec| bytes.starts_with(codec.magic()))
+    }
+
+    /// Returns the codec conventionally associated with a file extension, if any.
+    pub(crate) fn from_extension(extension: &OsStr) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|codec| extension.eq_ignore_ascii_case(codec.extension()))
+    }
+
+    /// Decompresses the entire input into `buf`.
+    fn read_to_end(self, bytes: &[u8], buf: &mut Vec<u8>) -> io::Result<usize> {
+        match self {
+            Self::Gzip => GzDecoder::new(bytes).read_to_end(buf),
+            #[cfg(feature = "bzip2")]
+            Self::Bzip2 => bzip2::read::MultiBzDecoder::new(bytes).read_to_end(buf),
+            #[cfg(feature = "xz")]
+            Self::Xz => lzma_rust2::XzReader::new(bytes, true).read_to_end(buf),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::stream::read::Decoder::with_buffer(bytes)?.read_to_end(buf),
+        }
+    }
+}
+
+/// An error that occurred while decompressing an NZB, along with the codec that failed.
+#[derive(Debug)]
+pub(crate) struct DecompressError {
+    pub(crate) codec: Codec,
+    pub(crate) source: io::Error,
+}
+
+/// Decompresses compressed input, returning it unchanged if it isn't compressed.
+///
+/// The compression format is detected by its magic bytes rather than by file extension.
+/// Pass `codec` to decompress the input with that codec regardless,
+/// e.g., because the file it came from has a `.gz` extension.
+pub(crate) fn decompress(bytes: &[u8], codec: Option<Codec>) -> Result<Cow<'_, [u8]>, DecompressError> {
+    let Some(codec) = codec.or_else(|| Codec::sniff(bytes)) else {
+        return Ok(Cow::Borrowed(bytes));
+    };
+
+    let mut decompressed = Vec::with_capacity(bytes.len());
+    codec
+        .read_to_end(bytes, &mut decompressed)
+        .map_err(|source| DecompressError { codec, source })?;
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Returns `true` if an entry in a zip archive looks like an NZB,
+/// i.e., it has an `.nzb` extension, optionally followed by the extension of a supported codec.
+///
+/// Directories, and entries in the `__MACOSX` directory, which holds the resource
+/// forks added by the macOS archive utility, are never NZBs.
+#[cfg(feature = "zip")]
+pub(crate) fn is_nzb_entry(name: &str) -> bool {
+    if name.starts_with("__MACOSX/") || name.ends_with('/') {
+        return false;
+    }
+
+    let path = std::path::Path::new(name);
+    let path = match path.extension() {
+        Some(extension) if Codec::from_extension(extension).is_some() => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("nzb"))
+}
+
+/// Strips the `.nzb` extension, along with that of a supported compression format,
+/// from the name of an NZB file, e.g., `release` for `release.nzb.gz`.
+pub(crate) fn nzb_stem(name: &str) -> &str {
+    let name = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && Codec::from_extension(OsStr::new(extension)).is_some() => stem,
+        _ => name,
+    };
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && extension.eq_ignore_ascii_case("nzb") => stem,
+        _ => name,
+    }
+}
+
+/// Errors that can occur while converting the raw bytes of an NZB into a string.
+#[derive(Debug)]
+pub(crate) enum DecodeError {
+    /// The bytes are not valid in the detected encoding, which is named.
+    Malformed(&'static str),
+    /// The XML declaration names an encoding that is not supported.
+    Unsupported(String),
+}
+
+/// Extracts the value of the `encoding` pseudo-attribute from the XML declaration, if any.
+///
+/// The declaration is only looked for at the very start of the document
+/// (after any leading whitespace), and is read as ASCII.
+fn declared_encoding(bytes: &[u8]) -> Option<&[u8]> {
+    let bytes = bytes.trim_ascii_start();
+
+    if bytes.len() < 5 || !bytes[..5].eq_ignore_ascii_case(b"<?xml") {
+        return None;
+    }
+
+    let end = bytes.windows(2).position(|w| w == b"?>")?;
+    let declaration = &bytes[5..end];
+
+    let start = declaration.windows(8).position(|w| w == b"encoding")?;
+    let rest = declaration[start + 8..].trim_ascii_start();
+    let rest = rest.strip_prefix(b"=")?.trim_ascii_start();
+
+    let (&quote, rest) = rest.split_first()?;
+    if quote != b'"' && quote != b'\'' {
+        return None;
+    }
+
+    let len = rest.iter().position(|&b| b == quote)?;
+    Some(rest[..len].trim_ascii())
+}
+
+/// Detects the character encoding of an NZB, returning it along with the
+/// length of the byte order mark to skip.
+///
+/// This follows the [autodetection rules of the XML specification][0]:
+/// 1. A byte order mark (UTF-8, UTF-16LE, or UTF-16BE).
+/// 2. UTF-16 without a byte order mark, recognized by how `<?` is encoded.
+/// 3. The `encoding` declared in the XML declaration.
+/// 4. UTF-8 otherwise.
+///
+/// [0]: <https://www.w3.org/TR/xml/#sec-guessing>
+fn detect_encoding(bytes: &[u8]) -> Result<(&'static Encoding, usize), DecodeError> {
+    if let Some(bom) = Encoding::for_bom(bytes) {
+        return Ok(bom);
+    }
+
+    if bytes.starts_with(b"<\0?\0") {
+        return Ok((UTF_16LE, 0));
+    }
+
+    if bytes.starts_with(b"\0<\0?") {
+        return Ok((UTF_16BE, 0));
+    }
+
+    let Some(label) = declared_encoding(bytes) else {
+        return Ok((UTF_8, 0));
+    };
+
+    match Encoding::for_label(label) {
+        // A declaration that could be read as ASCII cannot actually be in UTF-16,
+        // which usually means the document was transcoded without updating it.
+        Some(encoding) if encoding == UTF_16LE || encoding == UTF_16BE => Ok((UTF_8, 0)),
+        // The replacement encoding is a stand-in for encodings that are unsafe to decode.
+        Some(encoding) if encoding != REPLACEMENT => Ok((encoding, 0)),
+        _ => Err(DecodeError::Unsupported(String::from_utf8_lossy(label).into_owned())),
+    }
+}
+
+/// Converts the raw bytes of an NZB into a string, transcoding
+/// them into UTF-8 according to the detected encoding.
+///
+/// See [`detect_encoding`] for how the encoding is determined.
+///
+/// Bytes that are invalid in the detected encoding are reported as [`DecodeError::Malformed`].
+pub(crate) fn decode(bytes: Cow<'_, [u8]>) -> Result<String, DecodeError> {
+    let (encoding, bom_len) = detect_encoding(&bytes)?;
+
+    if encoding == UTF_8 {
+        let mut bytes = bytes.into_owned();
+        bytes.drain(..bom_len);
+        return String::from_utf8(bytes).map_err(|_| DecodeError::Malformed(UTF_8.name()));
+    }
+
+    encoding
+        .decode_without_bom_handling_and_without_replacement(&bytes[bom_len..])
+        .map(Cow::into_owned)
+        .ok_or(DecodeError::Malformed(encoding.name()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(Codec::sniff(&gzip(b"<nzb/>")), Some(Codec::Gzip));
+        assert_eq!(Codec::sniff(b"<nzb/>"), None);
+        assert_eq!(Codec::sniff(&[0x1f]), None);
+        assert_eq!(Codec::sniff(&[]), None);
+    }
+
+    #[test]
+    fn test_from_extension() {
+        assert_eq!(Codec::from_extension(OsStr::new("gz")), Some(Codec::Gzip));
+        assert_eq!(Codec::from_extension(OsStr::new("GZ")), Some(Codec::Gzip));
+        assert_eq!(Codec::from_extension(OsStr::new("nzb")), None);
+    }
+
+    #[test]
+    fn test_decompress() {
+        assert_eq!(decompress(&gzip(b"<nzb/>"), None).unwrap(), &b"<nzb/>"[..]);
+        assert_eq!(decompress(&gzip(b"<nzb/>"), Some(Codec::Gzip)).unwrap(), &b"<nzb/>"[..]);
+        assert!(matches!(decompress(b"<nzb/>", None).unwrap(), Cow::Borrowed(b"<nzb/>")));
+
+        let error = decompress(br#"<?xml version="1.0"?><nzb/>"#, Some(Codec::Gzip)).unwrap_err();
+        assert_eq!(error.codec, Codec::Gzip);
+        assert_eq!(error.source.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[cfg(feature = "zip")]
+    #[rstest]
+    #[case("abc.nzb", true)]
+    #[case("dir/ABC.NZB", true)]
+    #[case("abc.nzb.gz", true)]
+    #[case("abc.gz", false)]
+    #[case("abc.txt", false)]
+    #[case("abc.nzb/", false)]
+    #[case("nzb", false)]
+    #[case("__MACOSX/._abc.nzb", false)]
+    fn test_is_nzb_entry(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_nzb_entry(name), expected);
+    }
+
+    #[rstest]
+    #[case("release.nzb", "release")]
+    #[case("release.NZB.gz", "release")]
+    #[case("release.gz", "release")]
+    #[case("release.mkv.nzb", "release.mkv")]
+    #[case("release.txt", "release.txt")]
+    #[case(".nzb", ".nzb")]
+    fn test_nzb_stem(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(nzb_stem(name), expected);
+    }
+
+    #[rstest]
+    #[case(br#"<?xml version="1.0" encoding="iso-8859-1" ?>"#, Some(&b"iso-8859-1"[..]))]
+    #[case(br#"  <?XML version='1.0' encoding = 'UTF-8'?><nzb/>"#, Some(&b"UTF-8"[..]))]
+    #[case(br#"<?xml version="1.0"?><nzb encoding="latin1"/>"#, None)]
+    #[case(br#"<?xml version="1.0" encoding=latin1?>"#, None)]
+    #[case(br#"<?xml version="1.0" encoding="latin1"#, None)]
+    #[case(br#"<nzb/>"#, None)]
+    #[case(b"", None)]
+    fn test_declared_encoding(#[case] input: &[u8], #[case] expected: Option<&[u8]>) {
+        assert_eq!(declared_encoding(input), expected);
+    }
+
+    #[rstest]
+    #[case(b"<nzb/>", UTF_8, 0)]
+    #[case(b"\xEF\xBB\xBF<nzb/>", UTF_8, 3)]
+    #[case(b"\xFF\xFE<\0n\0", UTF_16LE, 2)]
+    #[case(b"\xFE\xFF\0<\0n", UTF_16BE, 2)]
+    #[case(b"<\0?\0x\0m\0l\0", UTF_16LE, 0)]
+    #[case(b"\0<\0?\0x\0m\0l", UTF_16BE, 0)]
+    #[case(br#"<?xml version="1.0" encoding="ISO-8859-1"?>"#, encoding_rs::WINDOWS_1252, 0)]
+    #[case(br#"<?xml version="1.0" encoding="windows-1251"?>"#, encoding_rs::WINDOWS_1251, 0)]
+    #[case(br#"<?xml version="1.0" encoding="utf-16"?>"#, UTF_8, 0)]
+    fn test_detect_encoding(#[case] input: &[u8], #[case] encoding: &'static Encoding, #[case] bom_len: usize) {
+        assert_eq!(detect_encoding(input).unwrap(), (encoding, bom_len));
+    }
+
+    #[rstest]
+    #[case(br#"<?xml version="1.0" encoding="ebcdic"?>"#, "ebcdic")]
+    #[case(br#"<?xml version="1.0" encoding="iso-2022-kr"?>"#, "iso-2022-kr")]
+    fn test_detect_unsupported_encoding(#[case] input: &[u8], #[case] label: &str) {
+        assert!(matches!(detect_encoding(input), Err(DecodeError::Unsupported(l)) if l == label));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(Cow::Borrowed(b"<nzb/>")).unwrap(), "<nzb/>");
+        assert_eq!(decode(Cow::Borrowed(b"\xEF\xBB\xBF<nzb/>")).unwrap(), "<nzb/>");
+        assert_eq!(
+            decode(Cow::Borrowed(b"<?xml encoding=\"latin1\"?><a>\xE9\x80</a>")).unwrap(),
+            "<?xml encoding=\"latin1\"?><a>\u{e9}\u{20ac}</a>"
+        );
+        assert_eq!(decode(Cow::Borrowed(b"\xFF\xFE<\0a\0/\0>\0")).unwrap(), "<a/>");
+        assert!(matches!(
+            decode(Cow::Borrowed(&[0xff])),
+            Err(DecodeError::Malformed("UTF-8"))
+        ));
+        assert!(matches!(
+            decode(Cow::Borrowed(b"\xFF\xFE<\0\x00\xD8")),
+            Err(DecodeError::Malformed("UTF-16LE"))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::subject;
+
+/// The kind of a file in an NZB, as indicated by its extension.
+///
+/// See [`File::kind`](crate::File::kind).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileKind {
+    /// Video files, e.g., `.mkv`, `.mp4`, `.avi`.
+    Video,
+    /// Audio files, e.g., `.flac`, `.mp3`, `.m4a`.
+    Audio,
+    /// Subtitle files, e.g., `.srt`, `.ass`, `.sub`.
+    Subtitle,
+    /// Image files, e.g., `.jpg`, `.png`.
+    Image,
+    /// `.nfo` files.
+    Nfo,
+    /// `.sfv` checksum files.
+    Sfv,
+    /// `.nzb` files.
+    Nzb,
+    /// `.par2` files.
+    Par2,
+    /// RAR volumes, i.e., `.rar` and `.r00` ... `.v99`.
+    Rar,
+    /// 7-Zip archives and their split volumes, i.e., `.7z` and `.7z.001`, `.7z.002`, ...
+    SevenZip,
+    /// Zip archives and their split volumes, i.e., `.zip` and `.z01`, `.z02`, ...
+    Zip,
+    /// Executables and other files that can run code when opened, e.g., `.exe`, `.scr`, `.lnk`.
+    ///
+    /// Such files have no place in a typical Usenet post and are likely malware.
+    Executable,
+    /// Any other file, including files without a recognized extension.
+    Other,
+}
+
+impl FileKind {
+    /// Classifies a file by its name (case-insensitive).
+    pub(crate) fn from_name(name: &str) -> Self {
+        if subject::is_par2(name) {
+            return Self::Par2;
+        }
+        if subject::is_rar(name) {
+            return Self::Rar;
+        }
+
+        let Some(ext) = subject::file_extension(name) else {
+            return Self::Other;
+        };
+        let ext = ext.to_ascii_lowercase();
+
+        match ext.as_str() {
+            "mkv" | "mp4" | "m4v" | "avi" | "mov" | "wmv" | "mpg" | "mpeg" | "ts" | "m2ts" | "webm" | "flv" | "vob"
+            | "ogv" | "divx" | "3gp" => Self::Video,
+            "mp3" | "flac" | "aac" | "m4a" | "ogg" | "opus" | "wav" | "wma" | "ape" | "ac3" | "dts" | "mka"
+            | "aiff" => Self::Audio,
+            "srt" | "ass" | "ssa" | "sub" | "idx" | "sup" | "vtt" | "smi" => Self::Subtitle,
+            "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "tif" | "tiff" => Self::Image,
+            "nfo" => Self::Nfo,
+            "sfv" => Self::Sfv,
+            "nzb" => Self::Nzb,
+            "7z" => Self::SevenZip,
+            "zip" => Self::Zip,
+            "exe" | "scr" | "lnk" | "com" | "pif" | "bat" | "cmd" | "msi" | "vbs" | "vbe" | "jse" | "wsf" | "hta"
+            | "cpl" | "ps1" | "jar" => Self::Executable,
+            // Split volumes, e.g., `.7z.001` or `.z01`.
+            _ if ext.len() == 3 && ext.bytes().all(|b| b.is_ascii_digit()) => {
+                let stem = subject::file_stem(name);
+                match subject::file_extension(stem) {
+                    Some(inner) if inner.eq_ignore_ascii_case("7z") => Self::SevenZip,
+                    _ => Self::Other,
+                }
+            }
+            _ if ext.len() == 3 && ext.starts_with('z') && ext[1..].bytes().all(|b| b.is_ascii_digit()) => Self::Zip,
+            _ => Self::Other,
+        }
+    }
+
+    /// Return [`true`] if files of this kind are archives that need to be extracted, [`false`] otherwise.
+    #[must_use]
+    pub fn is_archive(&self) -> bool {
+        matches!(self, Self::Rar | Self::SevenZip | Self::Zip)
+    }
+}
+
+impl std::fmt::Display for FileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Video => write!(f, "video"),
+            Self::Audio => write!(f, "audio"),
+            Self::Subtitle => write!(f, "subtitle"),
+            Self::Image => write!(f, "image"),
+            Self::Nfo => write!(f, "nfo"),
+            Self::Sfv => write!(f, "sfv"),
+            Self::Nzb => write!(f, "nzb"),
+            Self::Par2 => write!(f, "par2"),
+            Self::Rar => write!(f, "rar"),
+            Self::SevenZip => write!(f, "7z"),
+            Self::Zip => write!(f, "zip"),
+            Self::Executable => write!(f, "executable"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("Big Buck Bunny - S01E01.mkv", FileKind::Video)]
+    #[case("movie.MP4", FileKind::Video)]
+    #[case("01 - Track.flac", FileKind::Audio)]
+    #[case("movie.en.srt", FileKind::Subtitle)]
+    #[case("cover.jpg", FileKind::Image)]
+    #[case("movie.nfo", FileKind::Nfo)]
+    #[case("movie.sfv", FileKind::Sfv)]
+    #[case("movie.nzb", FileKind::Nzb)]
+    #[case("movie.vol00+01.par2", FileKind::Par2)]
+    #[case("movie.part01.rar", FileKind::Rar)]
+    #[case("movie.r00", FileKind::Rar)]
+    #[case("movie.7z", FileKind::SevenZip)]
+    #[case("movie.7z.001", FileKind::SevenZip)]
+    #[case("movie.zip", FileKind::Zip)]
+    #[case("movie.z01", FileKind::Zip)]
+    #[case("movie.exe", FileKind::Executable)]
+    #[case("Movie.mkv.scr", FileKind::Executable)]
+    #[case("movie.LNK", FileKind::Executable)]
+    #[case("movie.mkv.001", FileKind::Other)]
+    #[case("movie.xyz", FileKind::Other)]
+    #[case("movie", FileKind::Other)]
+    fn test_from_name(#[case] name: &str, #[case] expected: FileKind) {
+        assert_eq!(FileKind::from_name(name), expected);
+    }
+}
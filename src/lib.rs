@@ -1,16 +1,44 @@
 #![doc = include_str!("../README.md")]
 
+mod builder;
+mod diagnostic;
 mod errors;
 mod file;
+mod fileset;
+mod input;
+mod kind;
+mod merge;
 mod meta;
 mod nzb;
+mod obfuscation;
+mod par2;
 mod parser;
+mod rar;
+mod reader;
+#[cfg(feature = "release")]
+mod release;
 mod segment;
 mod subject;
+mod writer;
 mod xml;
 
-pub use crate::errors::{FileAttributeKind, ParseNzbError, ParseNzbFileError};
+pub use crate::builder::{FileBuilder, NzbBuilder};
+pub use crate::diagnostic::{Diagnostic, DiagnosticKind};
+pub use crate::errors::{
+    FileAttributeKind, FileLocation, ParseNzbError, ParseNzbFileError, ParseNzbReaderError, TextPosition,
+    WriteNzbFileError,
+};
 pub use crate::file::File;
+pub use crate::fileset::FileSet;
+pub use crate::kind::FileKind;
+pub use crate::merge::MergeReport;
 pub use crate::meta::Meta;
 pub use crate::nzb::Nzb;
+pub use crate::obfuscation::{ObfuscationAnalysis, ObfuscationDetector, ObfuscationRule};
+pub use crate::par2::Par2Volume;
+pub use crate::rar::RarVolume;
+pub use crate::reader::NzbReader;
+#[cfg(feature = "release")]
+pub use crate::release::{ReleaseCodec, ReleaseInfo, ReleaseSource};
 pub use crate::segment::Segment;
+pub use crate::subject::{Subject, SubjectCounter};
@@ -0,0 +1,127 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::errors::ParseNzbError;
+use crate::file::File;
+use crate::meta::Meta;
+use crate::nzb::Nzb;
+use crate::parser::validate_files;
+use crate::segment::Segment;
+
+/// What was deduplicated while merging NZBs with [`Nzb::merge`](crate::Nzb::merge).
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MergeReport {
+    nzbs: usize,
+    duplicate_files: Vec<String>,
+    duplicate_segments: usize,
+}
+
+impl MergeReport {
+    /// Number of NZBs that were merged.
+    #[must_use]
+    pub fn nzbs(&self) -> usize {
+        self.nzbs
+    }
+
+    /// Subjects of the files that were dropped because an identical file, with the same
+    /// segments, was already merged. Their groups are still added to that file.
+    #[must_use]
+    pub fn duplicate_files(&self) -> &[String] {
+        &self.duplicate_files
+    }
+
+    /// Number of segments that were dropped because a segment with the same Message-ID was already
+    /// merged into the same file, not counting those of [`MergeReport::duplicate_files`].
+    #[must_use]
+    pub fn duplicate_segments(&self) -> usize {
+        self.duplicate_segments
+    }
+
+    /// Return [`true`] if anything was deduplicated, [`false`] otherwise.
+    #[must_use]
+    pub fn has_duplicates(&self) -> bool {
+        !self.duplicate_files.is_empty() || self.duplicate_segments > 0
+    }
+}
+
+/// Message-IDs of the segments of a file.
+fn message_ids(file: &File) -> BTreeSet<&str> {
+    file.segments().iter().map(Segment::message_id).collect()
+}
+
+/// Files that were merged so far, and how to find the one a new file belongs to.
+#[derive(Default)]
+struct MergedFiles {
+    files: Vec<File>,
+    // Index in `files` of the file each Message-ID, file name and subject belongs to.
+    by_message_id: HashMap<String, usize>,
+    by_name: HashMap<String, usize>,
+    by_subject: HashMap<String, usize>,
+}
+
+impl MergedFiles {
+    /// Index of the file that `file` is a copy or a repost of, if any.
+    ///
+    /// Files sharing a segment are the same file. Otherwise, a repost is recognized by its file name,
+    /// since posters often tag or renumber the rest of the subject, or by its subject if it has no name.
+    fn find(&self, file: &File) -> Option<usize> {
+        file.segments()
+            .iter()
+            .find_map(|segment| self.by_message_id.get(segment.message_id()))
+            .or_else(|| match file.name() {
+                Some(name) => self.by_name.get(name),
+                None => self.by_subject.get(file.subject()),
+            })
+            .copied()
+    }
+
+    /// Remembers the Message-IDs, file name and subject of `file` as belonging to the file at `index`.
+    fn index(&mut self, file: &File, index: usize) {
+        for segment in file.segments() {
+            self.by_message_id
+                .entry(segment.message_id().to_owned())
+                .or_insert(index);
+        }
+        if let Some(name) = file.name() {
+            self.by_name.entry(name.to_owned()).or_insert(index);
+        }
+        self.by_subject.entry(file.subject().to_owned()).or_insert(index);
+    }
+}
+
+/// Merges the metadata and files of every NZB, see [`Nzb::merge`](crate::Nzb::merge).
+pub(crate) fn merge(nzbs: impl IntoIterator<Item = Nzb>) -> Result<(Nzb, MergeReport), ParseNzbError> {
+    let mut report = MergeReport::default();
+    let mut meta = Meta::default();
+    let mut source_name = None;
+    let mut merged = MergedFiles::default();
+
+    for nzb in nzbs {
+        report.nzbs += 1;
+        let (other_meta, other_files, other_source_name) = nzb.into_parts();
+        if report.nzbs == 1 {
+            source_name = other_source_name;
+        }
+        meta.merge(other_meta);
+
+        for file in other_files {
+            let Some(index) = merged.find(&file) else {
+                merged.index(&file, merged.files.len());
+                merged.files.push(file);
+                continue;
+            };
+
+            merged.index(&file, index);
+            let existing = &mut merged.files[index];
+            if message_ids(existing) == message_ids(&file) {
+                report.duplicate_files.push(file.subject().to_owned());
+                existing.merge(file);
+            } else {
+                report.duplicate_segments += existing.merge(file);
+            }
+        }
+    }
+
+    let files = validate_files(merged.files)?;
+    Ok((Nzb::new(meta, files, source_name), report))
+}
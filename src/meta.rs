@@ -1,3 +1,9 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
 /// NZB `<meta type="...">` values defined by the
 /// [`Metadata Defined Types` in the NZB specification][0].
 ///
@@ -29,29 +35,163 @@ impl MetaType {
     }
 }
 
+/// An IMDb title ID, e.g., `tt1727587`.
+static IMDB_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^tt\d{7,10}$").unwrap());
+
+/// An IMDb title ID in an IMDb URL, e.g., `https://www.imdb.com/title/tt1727587/`.
+static IMDB_URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bimdb\.com/title/(tt\d{7,10})\b").unwrap());
+
+/// A TVDB series ID in a TVDB URL, e.g., `https://thetvdb.com/?tab=series&id=81189`.
+static TVDB_URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bthetvdb\.com\S*?(?:[?&](?:id|seriesid)=|/series/)(\d+)\b").unwrap());
+
 /// Represents optional creator-definable metadata in an NZB.
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Every `<meta type="...">` entry is kept in document order, along with its type as written,
+/// including types not defined by the specification, such as `x-dnzb-*` or `imdb`.
+/// Types are compared case-insensitively.
+///
+/// Two [`Meta`] instances are equal if their title, passwords, tags and category are, and so are
+/// their entries of other types, in order. Neither the case of the types nor the position of the
+/// entries defined by the specification relative to the other entries is taken into account.
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SerdeMeta"))]
 pub struct Meta {
     title: Option<String>,
     passwords: Vec<String>,
     tags: Vec<String>,
     category: Option<String>,
+    /// Every entry in document order, from which the fields above are derived.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    entries: Vec<(String, String)>,
+}
+
+/// The serialized form of [`Meta`], which may lack the entries if it predates them.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerdeMeta {
+    title: Option<String>,
+    passwords: Vec<String>,
+    tags: Vec<String>,
+    category: Option<String>,
+    #[serde(default)]
+    entries: Vec<(String, String)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerdeMeta> for Meta {
+    fn from(meta: SerdeMeta) -> Self {
+        if meta.entries.is_empty() {
+            Self::new(meta.title, meta.passwords, meta.tags, meta.category)
+        } else {
+            meta.entries.into_iter().collect()
+        }
+    }
+}
+
+/// A meta type that is compared case-insensitively.
+#[derive(Clone, Copy, Debug)]
+struct Caseless<'a>(&'a str);
+
+impl Caseless<'_> {
+    fn bytes(&self) -> impl Iterator<Item = u8> {
+        self.0.bytes().map(|b| b.to_ascii_lowercase())
+    }
+}
+
+impl PartialEq for Caseless<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(other.0)
+    }
+}
+
+impl Eq for Caseless<'_> {}
+
+impl PartialOrd for Caseless<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Caseless<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes().cmp(other.bytes())
+    }
+}
+
+impl Hash for Caseless<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes().for_each(|b| state.write_u8(b));
+        state.write_u8(0xff);
+    }
+}
+
+impl PartialEq for Meta {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title
+            && self.passwords == other.passwords
+            && self.tags == other.tags
+            && self.category == other.category
+            && self.others().eq(other.others())
+    }
+}
+
+impl Eq for Meta {}
+
+impl PartialOrd for Meta {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Meta {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.title
+            .cmp(&other.title)
+            .then_with(|| self.passwords.cmp(&other.passwords))
+            .then_with(|| self.tags.cmp(&other.tags))
+            .then_with(|| self.category.cmp(&other.category))
+            .then_with(|| self.others().cmp(other.others()))
+    }
+}
+
+impl Hash for Meta {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.title.hash(state);
+        self.passwords.hash(state);
+        self.tags.hash(state);
+        self.category.hash(state);
+        self.others().for_each(|entry| entry.hash(state));
+    }
+}
+
+impl<T: Into<String>, V: Into<String>> FromIterator<(T, V)> for Meta {
+    /// Creates a [`Meta`] instance from `(type, value)` entries, in order.
+    fn from_iter<I: IntoIterator<Item = (T, V)>>(iter: I) -> Self {
+        let mut meta = Self {
+            entries: iter
+                .into_iter()
+                .map(|(typ, value)| (typ.into(), value.into()))
+                .collect(),
+            ..Self::default()
+        };
+        meta.refresh();
+        meta
+    }
 }
 
 impl Meta {
     /// Parse `<meta>` fields from an NZB document.
     ///
-    /// Extracts metadata from `<meta>` elements under `<head>` according
-    /// to the [NZB specification’s `Metadata Defined Types`][0].
-    ///
-    /// Supported types:
+    /// Extracts metadata from `<meta>` elements under `<head>`, including the
+    /// [NZB specification’s `Metadata Defined Types`][0]:
     /// - `title` (single)
     /// - `password` (multiple allowed)
     /// - `tag` (multiple allowed)
     /// - `category` (single)
     ///
-    /// Unknown `<meta type="...">` values are ignored. If multiple
+    /// Every entry is kept, whatever its type. If multiple
     /// `title` or `category` entries are present, the first one wins.
     ///
     /// [0]: <https://sabnzbd.org/wiki/extra/nzb-spec>
@@ -67,37 +207,58 @@ impl Meta {
     /// </head>
     /// ```
     pub(crate) fn parse(nzb: &roxmltree::Document) -> Self {
-        let mut title: Option<String> = None;
-        let mut passwords: Vec<String> = Vec::new();
-        let mut tags: Vec<String> = Vec::new();
-        let mut category: Option<String> = None;
-
-        for meta in nzb.descendants().filter(|n| n.has_tag_name("meta")) {
-            let Some(typ) = meta.attribute("type").and_then(MetaType::parse) else {
-                continue;
-            };
+        let mut meta = Meta::default();
 
-            let Some(text) = meta.text().map(String::from) else {
-                continue;
-            };
-
-            match typ {
-                MetaType::Title => title = title.or(Some(text)),
-                MetaType::Password => passwords.push(text),
-                MetaType::Tag => tags.push(text),
-                MetaType::Category => category = category.or(Some(text)),
+        for node in nzb.descendants().filter(|n| n.has_tag_name("meta")) {
+            if let Some(typ) = node.attribute("type")
+                && let Some(text) = node.text()
+            {
+                meta.append(typ, text);
             }
         }
 
-        Meta {
-            title,
-            passwords,
-            tags,
-            category,
+        meta
+    }
+
+    /// Derives the title, passwords, tags and category from the entries again after they changed.
+    fn refresh(&mut self) {
+        let title = self.values(MetaType::Title).next().map(str::to_owned);
+        let category = self.values(MetaType::Category).next().map(str::to_owned);
+        self.passwords = self.values(MetaType::Password).map(str::to_owned).collect();
+        self.tags = self.values(MetaType::Tag).map(str::to_owned).collect();
+        self.title = title;
+        self.category = category;
+    }
+
+    /// Entries of types not defined by the specification, in document order.
+    fn others(&self) -> impl Iterator<Item = (Caseless<'_>, &str)> {
+        self.iter()
+            .filter(|(typ, _)| MetaType::parse(typ).is_none())
+            .map(|(typ, value)| (Caseless(typ), value))
+    }
+
+    /// Adds the entries of `other` that are not present yet, in order.
+    ///
+    /// A `title` or `category` is only added if there is none yet, so the first one wins,
+    /// while entries of any other type, such as `password` or `tag`, are added unless
+    /// there already is one with the same type and value.
+    pub(crate) fn merge(&mut self, other: Meta) {
+        for (typ, value) in other.entries {
+            let present = match MetaType::parse(&typ) {
+                Some(single @ (MetaType::Title | MetaType::Category)) => self.values(single).next().is_some(),
+                _ => self.iter().any(|(t, v)| t.eq_ignore_ascii_case(&typ) && v == value),
+            };
+            if !present {
+                self.entries.push((typ, value));
+            }
         }
+        self.refresh();
     }
 
     /// Creates a new [`Meta`] instance.
+    ///
+    /// The entries are ordered as the arguments. Use [`Meta::from_iter`]
+    /// to include other types or choose a different order.
     #[must_use]
     pub fn new(
         title: Option<impl Into<String>>,
@@ -105,12 +266,88 @@ impl Meta {
         tags: impl IntoIterator<Item = impl Into<String>>,
         category: Option<impl Into<String>>,
     ) -> Self {
-        Self {
-            title: title.map(Into::into),
-            passwords: passwords.into_iter().map(Into::into).collect(),
-            tags: tags.into_iter().map(Into::into).collect(),
-            category: category.map(Into::into),
+        let title = title.map(|title| ("title", title.into()));
+        let passwords = passwords.into_iter().map(|password| ("password", password.into()));
+        let tags = tags.into_iter().map(|tag| ("tag", tag.into()));
+        let category = category.map(|category| ("category", category.into()));
+        title.into_iter().chain(passwords).chain(tags).chain(category).collect()
+    }
+
+    /// Every entry as a `(type, value)` pair, in document order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(typ, value)| (typ.as_str(), value.as_str()))
+    }
+
+    /// Return [`true`] if there are no entries, [`false`] otherwise.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Value of the first entry of the given type (case-insensitive), e.g., `"x-dnzb-imdb"`.
+    #[must_use]
+    pub fn get(&self, typ: &str) -> Option<&str> {
+        self.get_all(typ).next()
+    }
+
+    /// Values of every entry of the given type (case-insensitive), in document order.
+    pub fn get_all(&self, typ: &str) -> impl Iterator<Item = &str> {
+        self.iter()
+            .filter(move |(t, _)| t.eq_ignore_ascii_case(typ))
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of the given type (case-insensitive), replacing every entry of that type.
+    ///
+    /// The first entry of that type keeps its position, otherwise a new entry is appended.
+    pub fn set(&mut self, typ: &str, value: impl Into<String>) {
+        let mut value = Some(value.into());
+        self.entries.retain_mut(|(t, v)| {
+            if !t.eq_ignore_ascii_case(typ) {
+                return true;
+            }
+            // Only the first entry of the type is kept, with the new value.
+            match value.take() {
+                Some(value) => {
+                    *v = value;
+                    true
+                }
+                None => false,
+            }
+        });
+        if let Some(value) = value {
+            self.entries.push((typ.to_owned(), value));
         }
+        self.refresh();
+    }
+
+    /// Appends an entry of the given type, e.g., another `password`.
+    pub fn append(&mut self, typ: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((typ.into(), value.into()));
+        self.refresh();
+    }
+
+    /// Removes every entry of the given type (case-insensitive), returning their values in document order.
+    pub fn remove(&mut self, typ: &str) -> Vec<String> {
+        let (removed, kept) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(t, _)| t.eq_ignore_ascii_case(typ));
+        self.entries = kept;
+        self.refresh();
+        removed.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Keeps only the entries for which `f` returns [`true`] when given their type and value.
+    pub fn retain(&mut self, mut f: impl FnMut(&str, &str) -> bool) {
+        self.entries.retain(|(typ, value)| f(typ, value));
+        self.refresh();
+    }
+
+    /// Values of every entry of a type defined by the specification, in document order.
+    fn values(&self, typ: MetaType) -> impl Iterator<Item = &str> {
+        self.iter()
+            .filter(move |(t, _)| MetaType::parse(t) == Some(typ))
+            .map(|(_, value)| value)
     }
 
     /// Human-readable title associated with the NZB.
@@ -138,6 +375,176 @@ impl Meta {
     }
 }
 
+/// Passwords embedded in job names, as understood by SABnzbd and NZBGet.
+impl Meta {
+    /// Splits a password off a job name, such as the title or the name of the NZB file without its extension,
+    /// returning the cleaned name and the password, if any.
+    ///
+    /// Two conventions are recognized: `Name{{password}}` and `Name / password`. A name is left untouched
+    /// if it contains a URL, or if nothing but dots and spaces would be left of it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::Meta;
+    ///
+    /// assert_eq!(Meta::split_password("Sintel{{secret}}"), ("Sintel", Some("secret")));
+    /// assert_eq!(Meta::split_password("Sintel / secret"), ("Sintel", Some("secret")));
+    /// assert_eq!(Meta::split_password("AC/DC Live"), ("AC/DC Live", None));
+    /// ```
+    #[must_use]
+    pub fn split_password(name: &str) -> (&str, Option<&str>) {
+        if name.contains("://") {
+            return (name, None);
+        }
+
+        let braces = name.get(1..).and_then(|rest| rest.find("{{")).map(|index| index + 1);
+        let slash = name
+            .find(" / ")
+            .filter(|&slash| braces.is_none_or(|braces| slash < braces));
+        let split = match (slash, braces) {
+            (Some(slash), _) => Some((&name[..slash], &name[slash + 3..])),
+            (None, Some(braces)) => name
+                .rfind("}}")
+                .filter(|&closing| closing > braces)
+                .map(|closing| (&name[..braces], &name[braces + 2..closing])),
+            (None, None) => None,
+        };
+
+        match split {
+            Some((cleaned, password)) => {
+                let cleaned = cleaned.trim_matches(['.', ' ']);
+                if cleaned.is_empty() || password.is_empty() {
+                    (name, None)
+                } else {
+                    (cleaned, Some(password))
+                }
+            }
+            None => (name, None),
+        }
+    }
+
+    /// Moves a password embedded in the title, see [`Meta::split_password`], into a `password` entry.
+    pub(crate) fn extract_title_password(&mut self) {
+        let Some((_, title)) = self
+            .entries
+            .iter_mut()
+            .find(|(typ, _)| MetaType::parse(typ) == Some(MetaType::Title))
+        else {
+            return;
+        };
+
+        if let (cleaned, Some(password)) = Self::split_password(title) {
+            let (cleaned, password) = (cleaned.to_owned(), password.to_owned());
+            *title = cleaned;
+            self.refresh();
+            self.add_password(&password);
+        }
+    }
+
+    /// Adds a `password` entry, unless there already is one with the same value.
+    pub(crate) fn add_password(&mut self, password: &str) {
+        if !self.passwords.iter().any(|p| p == password) {
+            self.append("password", password);
+        }
+    }
+}
+
+/// Accessors for the metadata that indexers conventionally add, following the `X-DNZB-*`
+/// headers understood by SABnzbd and NZBGet, e.g., `<meta type="x-dnzb-propername">`.
+///
+/// Values are trimmed, and entries that are empty or not in the expected format are skipped.
+impl Meta {
+    /// The first valid value among the entries of any of the given types, in document order.
+    fn first_valid<'a, T>(&'a self, types: &[&str], parse: impl Fn(&'a str) -> Option<T>) -> Option<T> {
+        self.iter()
+            .filter(|(typ, _)| types.iter().any(|t| t.eq_ignore_ascii_case(typ)))
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+            .find_map(parse)
+    }
+
+    /// Page with more information about the contents, such as its IMDb or TVDB page,
+    /// from `x-dnzb-moreinfo`. Only `http` and `https` URLs are accepted.
+    #[must_use]
+    pub fn more_info_url(&self) -> Option<&str> {
+        self.first_valid(&["x-dnzb-moreinfo"], |url| {
+            let scheme = url.split_once("://").map(|(scheme, _)| scheme)?;
+            let valid = (scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+                && !url.contains(char::is_whitespace);
+            valid.then_some(url)
+        })
+    }
+
+    /// Properly formatted name of the contents, e.g., `"Sintel (2010)"`, from `x-dnzb-propername`.
+    #[must_use]
+    pub fn proper_name(&self) -> Option<&str> {
+        self.first_valid(&["x-dnzb-propername"], Some)
+    }
+
+    /// Name of the episode, for TV shows, from `x-dnzb-episodename`.
+    #[must_use]
+    pub fn episode_name(&self) -> Option<&str> {
+        self.first_valid(&["x-dnzb-episodename"], Some)
+    }
+
+    /// Category assigned by the indexer, e.g., `"TV > HD"`, from `x-dnzb-category`.
+    ///
+    /// Unlike [`Meta::category`], this follows the naming of the indexer rather than the NZB creator.
+    #[must_use]
+    pub fn indexer_category(&self) -> Option<&str> {
+        self.first_valid(&["x-dnzb-category"], Some)
+    }
+
+    /// Rating of the contents as given by the indexer, e.g., `7.5`, from `x-dnzb-rating`.
+    /// Negative and non-finite ratings are skipped.
+    #[must_use]
+    pub fn rating(&self) -> Option<f32> {
+        self.first_valid(&["x-dnzb-rating"], |rating| {
+            rating
+                .parse::<f32>()
+                .ok()
+                .filter(|rating| rating.is_finite() && *rating >= 0.0)
+        })
+    }
+
+    /// IMDb title ID of the contents, e.g., `"tt1727587"`, from `imdb` or `x-dnzb-imdb`,
+    /// or, failing that, from an IMDb URL in [`Meta::more_info_url`].
+    #[must_use]
+    pub fn imdb_id(&self) -> Option<&str> {
+        self.first_valid(&["imdb", "imdbid", "x-dnzb-imdb"], |id| {
+            IMDB_ID.is_match(id).then_some(id)
+        })
+        .or_else(|| {
+            let url = self.more_info_url()?;
+            IMDB_URL
+                .captures(url)
+                .and_then(|captures| captures.get(1))
+                .map(|id| id.as_str())
+        })
+    }
+
+    /// TVDB series ID of the contents, e.g., `81189`, from `tvdb` or `x-dnzb-tvdb`,
+    /// or, failing that, from a TVDB URL in [`Meta::more_info_url`].
+    #[must_use]
+    pub fn tvdb_id(&self) -> Option<u32> {
+        let parse = |id: &str| {
+            id.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| id.parse().ok())
+                .flatten()
+                .filter(|&id| id > 0)
+        };
+        self.first_valid(&["tvdb", "tvdbid", "x-dnzb-tvdb"], parse).or_else(|| {
+            let url = self.more_info_url()?;
+            TVDB_URL
+                .captures(url)
+                .and_then(|captures| captures.get(1))
+                .and_then(|id| parse(id.as_str()))
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use roxmltree::Document;
@@ -225,7 +632,8 @@ mod tests {
             "#,
         );
 
-        assert_eq!(meta.title.as_deref(), Some("First"));
+        assert_eq!(meta.title(), Some("First"));
+        assert_eq!(meta.get_all("title").collect::<Vec<_>>(), vec!["First", "Second"]);
     }
 
     #[test]
@@ -245,14 +653,14 @@ mod tests {
     }
 
     #[test]
-    fn test_ignores_unknown_meta_types() {
+    fn test_keeps_unknown_meta_types() {
         let meta = parse_metadata_from_xml(
             r#"
             <nzb>
                 <head>
                     <meta type="title">Known</meta>
-                    <meta type="x-custom">Ignored</meta>
-                    <meta type="nonsense">Ignored</meta>
+                    <meta type="x-custom">Custom</meta>
+                    <meta type="nonsense">Nonsense</meta>
                 </head>
             </nzb>
             "#,
@@ -262,6 +670,41 @@ mod tests {
         assert!(meta.passwords().is_empty());
         assert!(meta.tags().is_empty());
         assert!(meta.category().is_none());
+        assert_eq!(meta.get("X-Custom"), Some("Custom"));
+        assert_eq!(
+            meta.iter().collect::<Vec<_>>(),
+            vec![("title", "Known"), ("x-custom", "Custom"), ("nonsense", "Nonsense")]
+        );
+    }
+
+    #[test]
+    fn test_equality_ignores_type_case_and_position() {
+        let parsed = parse_metadata_from_xml(
+            r#"
+            <nzb>
+                <head>
+                    <meta type="x-dnzb-imdb">tt1727587</meta>
+                    <meta type="TITLE">Sintel</meta>
+                    <meta type="Password">secret</meta>
+                </head>
+            </nzb>
+            "#,
+        );
+        let mut built = Meta::new(Some("Sintel"), ["secret"], Vec::<String>::new(), None::<String>);
+        assert_ne!(parsed, built);
+        built.append("X-DNZB-IMDB", "tt1727587");
+        assert_eq!(parsed, built);
+        assert_eq!(parsed.cmp(&built), Ordering::Equal);
+
+        let hash = |meta: &Meta| {
+            let mut hasher = std::hash::DefaultHasher::new();
+            meta.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&parsed), hash(&built));
+
+        built.set("x-dnzb-imdb", "tt0111161");
+        assert_ne!(parsed, built);
     }
 
     #[test]
@@ -279,4 +722,185 @@ mod tests {
         assert_eq!(meta.title(), Some("Upper"));
         assert_eq!(meta.passwords(), vec!["secret"]);
     }
+
+    #[test]
+    fn test_dnzb_accessors() {
+        let meta = parse_metadata_from_xml(
+            r#"
+            <nzb>
+                <head>
+                    <meta type="X-DNZB-MoreInfo">https://www.imdb.com/title/tt1727587/</meta>
+                    <meta type="x-dnzb-propername"> Sintel (2010) </meta>
+                    <meta type="x-dnzb-episodename">Pilot</meta>
+                    <meta type="x-dnzb-category">Movies > HD</meta>
+                    <meta type="x-dnzb-rating">7.5</meta>
+                    <meta type="tvdb">81189</meta>
+                </head>
+            </nzb>
+            "#,
+        );
+
+        assert_eq!(meta.more_info_url(), Some("https://www.imdb.com/title/tt1727587/"));
+        assert_eq!(meta.proper_name(), Some("Sintel (2010)"));
+        assert_eq!(meta.episode_name(), Some("Pilot"));
+        assert_eq!(meta.indexer_category(), Some("Movies > HD"));
+        assert_eq!(meta.category(), None);
+        assert_eq!(meta.rating(), Some(7.5));
+        assert_eq!(meta.imdb_id(), Some("tt1727587"));
+        assert_eq!(meta.tvdb_id(), Some(81189));
+        assert_eq!(Meta::default().imdb_id(), None);
+        assert_eq!(Meta::default().tvdb_id(), None);
+    }
+
+    #[rstest]
+    #[case(&[("imdb", "tt1727587")], Some("tt1727587"))]
+    #[case(&[("x-dnzb-imdb", " tt0111161 ")], Some("tt0111161"))]
+    #[case(&[("imdb", "1727587")], None)]
+    #[case(&[("imdb", "tt123")], None)]
+    #[case(&[("imdb", "tt1727587x")], None)]
+    #[case(&[("imdb", "invalid"), ("imdb", "tt1727587")], Some("tt1727587"))]
+    #[case(&[("x-dnzb-moreinfo", "http://imdb.com/title/tt1727587")], Some("tt1727587"))]
+    #[case(&[("x-dnzb-moreinfo", "https://www.imdb.com/name/nm0000001/")], None)]
+    fn test_imdb_id(#[case] entries: &[(&str, &str)], #[case] expected: Option<&str>) {
+        let meta = entries.iter().copied().collect::<Meta>();
+        assert_eq!(meta.imdb_id(), expected);
+    }
+
+    #[rstest]
+    #[case(&[("tvdb", "81189")], Some(81189))]
+    #[case(&[("x-dnzb-tvdb", "81189")], Some(81189))]
+    #[case(&[("tvdb", "0")], None)]
+    #[case(&[("tvdb", "+81189")], None)]
+    #[case(&[("tvdb", "tt1727587")], None)]
+    #[case(&[("tvdb", "99999999999")], None)]
+    #[case(&[("x-dnzb-moreinfo", "https://thetvdb.com/?tab=series&id=81189")], Some(81189))]
+    #[case(&[("x-dnzb-moreinfo", "https://thetvdb.com/series/81189")], Some(81189))]
+    #[case(&[("x-dnzb-moreinfo", "https://thetvdb.com/series/breaking-bad")], None)]
+    fn test_tvdb_id(#[case] entries: &[(&str, &str)], #[case] expected: Option<u32>) {
+        let meta = entries.iter().copied().collect::<Meta>();
+        assert_eq!(meta.tvdb_id(), expected);
+    }
+
+    #[rstest]
+    #[case("https://www.imdb.com/title/tt1727587/", true)]
+    #[case("HTTP://example.com", true)]
+    #[case("ftp://example.com", false)]
+    #[case("www.imdb.com/title/tt1727587/", false)]
+    #[case("https://example.com/a b", false)]
+    #[case("javascript:alert(1)", false)]
+    fn test_more_info_url(#[case] url: &str, #[case] valid: bool) {
+        let meta = [("x-dnzb-moreinfo", url)].into_iter().collect::<Meta>();
+        assert_eq!(meta.more_info_url().is_some(), valid);
+    }
+
+    #[rstest]
+    #[case("7.5", Some(7.5))]
+    #[case("10", Some(10.0))]
+    #[case("-1", None)]
+    #[case("NaN", None)]
+    #[case("good", None)]
+    fn test_rating(#[case] rating: &str, #[case] expected: Option<f32>) {
+        let meta = [("x-dnzb-rating", rating)].into_iter().collect::<Meta>();
+        assert_eq!(meta.rating(), expected);
+    }
+
+    #[rstest]
+    #[case("Sintel{{secret}}", "Sintel", Some("secret"))]
+    #[case("Sintel.2010 {{se cr}}et}}", "Sintel.2010", Some("se cr}}et"))]
+    #[case("Sintel / secret", "Sintel", Some("secret"))]
+    #[case("Sintel / secret / more", "Sintel", Some("secret / more"))]
+    #[case("Sintel / {{secret}}", "Sintel", Some("{{secret}}"))]
+    #[case("Sintel{{a / b}}", "Sintel", Some("a / b"))]
+    #[case("Sintel", "Sintel", None)]
+    #[case("AC/DC Live", "AC/DC Live", None)]
+    #[case("{{secret}}", "{{secret}}", None)]
+    #[case("..{{secret}}", "..{{secret}}", None)]
+    #[case("Sintel{{}}", "Sintel{{}}", None)]
+    #[case("Sintel{{secret", "Sintel{{secret", None)]
+    #[case("Sintel / ", "Sintel / ", None)]
+    #[case("https://example.com / secret", "https://example.com / secret", None)]
+    fn test_split_password(#[case] name: &str, #[case] cleaned: &str, #[case] password: Option<&str>) {
+        assert_eq!(Meta::split_password(name), (cleaned, password));
+    }
+
+    #[test]
+    fn test_extract_title_password() {
+        let mut meta = [
+            ("title", "Sintel / secret"),
+            ("password", "secret"),
+            ("title", "Other{{pw}}"),
+        ]
+        .into_iter()
+        .collect::<Meta>();
+        meta.extract_title_password();
+        assert_eq!(meta.title(), Some("Sintel"));
+        assert_eq!(meta.passwords(), vec!["secret"]);
+
+        meta.add_password("other");
+        assert_eq!(meta.passwords(), vec!["secret", "other"]);
+    }
+
+    #[test]
+    fn test_editing_entries() {
+        let mut meta = [
+            ("title", "First"),
+            ("password", "one"),
+            ("TITLE", "Second"),
+            ("password", "two"),
+        ]
+        .into_iter()
+        .collect::<Meta>();
+
+        meta.set("Title", "Edited");
+        meta.set("category", "TV");
+        meta.append("x-dnzb-moreinfo", "https://example.com");
+        assert_eq!(
+            meta.iter().collect::<Vec<_>>(),
+            vec![
+                ("title", "Edited"),
+                ("password", "one"),
+                ("password", "two"),
+                ("category", "TV"),
+                ("x-dnzb-moreinfo", "https://example.com"),
+            ]
+        );
+
+        assert_eq!(meta.remove("PASSWORD"), vec!["one", "two"]);
+        assert!(meta.passwords().is_empty());
+        assert!(meta.remove("password").is_empty());
+
+        meta.retain(|typ, _| !typ.starts_with("x-dnzb-"));
+        assert_eq!(
+            meta,
+            Meta::new(Some("Edited"), Vec::<String>::new(), Vec::<String>::new(), Some("TV"))
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut meta = Meta::new(Some("First"), ["one"], ["HD"], None::<String>);
+        meta.merge(
+            [
+                ("title", "Second"),
+                ("category", "TV"),
+                ("PASSWORD", "one"),
+                ("password", "two"),
+                ("tag", "HD"),
+                ("x-dnzb-imdb", "tt1727587"),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(
+            meta.iter().collect::<Vec<_>>(),
+            vec![
+                ("title", "First"),
+                ("password", "one"),
+                ("tag", "HD"),
+                ("category", "TV"),
+                ("password", "two"),
+                ("x-dnzb-imdb", "tt1727587"),
+            ]
+        );
+    }
 }
@@ -1,37 +1,100 @@
+use std::cmp::{Ordering, Reverse};
+#[cfg(feature = "zip")]
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::fs;
-use std::io::Read;
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "zip")]
+use std::io::Seek;
+use std::io::{self, BufWriter, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 
-use flate2::read::GzDecoder;
+use flate2::Compression;
+use flate2::write::GzEncoder;
 
-use crate::errors::{ParseNzbError, ParseNzbFileError};
+use crate::builder::NzbBuilder;
+use crate::diagnostic::Diagnostic;
+use crate::errors::{ParseNzbError, ParseNzbFileError, ParseNzbReaderError, WriteNzbFileError};
 use crate::file::File;
+use crate::fileset::FileSet;
+use crate::input::{self, Codec};
+use crate::kind::FileKind;
+use crate::merge::{self, MergeReport};
 use crate::meta::Meta;
 use crate::parser::parse_files;
-use crate::xml;
+#[cfg(feature = "release")]
+use crate::release::ReleaseInfo;
+use crate::{subject, writer, xml};
 
 /// Represents an NZB.
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Two NZBs are equal if their metadata and files are, regardless of the file they were parsed from.
+#[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Nzb {
     meta: Meta,
     files: Vec<File>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    source_name: Option<String>,
+}
+
+impl PartialEq for Nzb {
+    fn eq(&self, other: &Self) -> bool {
+        self.meta == other.meta && self.files == other.files
+    }
+}
+
+impl Eq for Nzb {}
+
+impl PartialOrd for Nzb {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Nzb {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.meta.cmp(&other.meta).then_with(|| self.files.cmp(&other.files))
+    }
+}
+
+impl Hash for Nzb {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.meta.hash(state);
+        self.files.hash(state);
+    }
 }
 
 impl FromStr for Nzb {
     type Err = ParseNzbError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nzb = xml::parse_document(s)?;
-        let meta = Meta::parse(&nzb);
-        let files = parse_files(&nzb)?;
-        Ok(Self { meta, files })
+        Self::parse_with_diagnostics(s, false).map(|(nzb, _)| nzb)
     }
 }
 
 impl Nzb {
+    pub(crate) fn new(meta: Meta, files: Vec<File>, source_name: Option<String>) -> Self {
+        Self {
+            meta,
+            files,
+            source_name,
+        }
+    }
+
+    /// Splits the [`Nzb`] into its metadata, files, and the name of the file it was parsed from.
+    pub(crate) fn into_parts(self) -> (Meta, Vec<File>, Option<String>) {
+        (self.meta, self.files, self.source_name)
+    }
+
+    /// Returns an [`NzbBuilder`] for constructing an [`Nzb`] programmatically.
+    #[must_use]
+    pub fn builder() -> NzbBuilder {
+        NzbBuilder::new()
+    }
+
     /// Parses a string into an [`Nzb`] instance.
     ///
     /// # Errors
@@ -72,13 +135,179 @@ impl Nzb {
         nzb.as_ref().parse()
     }
 
+    /// Parses a string into an [`Nzb`] instance, along with a [`Diagnostic`]
+    /// for every element that had to be skipped to do so.
+    ///
+    /// [`Nzb::parse`] silently skips segments with a missing or invalid `bytes` or `number`
+    /// attribute or an empty Message-ID, as well as empty groups. This produces the same [`Nzb`],
+    /// but also reports what was skipped, so that degraded NZBs can be flagged before downloading.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbError`] as [`Nzb::parse`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{DiagnosticKind, Nzb, ParseNzbError};
+    ///
+    /// fn main() -> Result<(), ParseNzbError> {
+    ///     let xml = r#"
+    ///         <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+    ///             <file poster="John &lt;nzb@nowhere.example&gt;" date="1706440708" subject="[1/1] - &quot;Big Buck Bunny - S01E01.mkv&quot; yEnc (1/2) 1478616">
+    ///                 <groups>
+    ///                     <group>alt.binaries.boneless</group>
+    ///                 </groups>
+    ///                 <segments>
+    ///                     <segment bytes="739067" number="1">9cacde4c986547369becbf97003fb2c5-9483514693959@example</segment>
+    ///                     <segment number="2">70a3a038ce324e618e2751e063d6a036-7285710986748@example</segment>
+    ///                 </segments>
+    ///             </file>
+    ///         </nzb>
+    ///         "#;
+    ///     let (nzb, diagnostics) = Nzb::parse_lenient(xml)?;
+    ///     assert_eq!(nzb.file().segments().len(), 1);
+    ///     assert_eq!(diagnostics.len(), 1);
+    ///     assert_eq!(diagnostics[0].file_index(), 0);
+    ///     assert_eq!(diagnostics[0].element_index(), 1);
+    ///     assert_eq!(diagnostics[0].kind(), DiagnosticKind::SegmentBytes);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn parse_lenient(nzb: impl AsRef<str>) -> Result<(Self, Vec<Diagnostic>), ParseNzbError> {
+        Self::parse_with_diagnostics(nzb.as_ref(), false)
+    }
+
+    /// Parses a string into an [`Nzb`] instance, rejecting any element
+    /// that [`Nzb::parse`] would silently skip.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbError`] as [`Nzb::parse`], and additionally
+    /// [`ParseNzbError::Diagnostic`] for the first element [`Nzb::parse_lenient`] would report.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{DiagnosticKind, Nzb, ParseNzbError};
+    ///
+    /// let xml = r#"
+    ///     <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+    ///         <file poster="John &lt;nzb@nowhere.example&gt;" date="1706440708" subject="[1/1] - &quot;Big Buck Bunny - S01E01.mkv&quot; yEnc (1/2) 1478616">
+    ///             <groups>
+    ///                 <group>alt.binaries.boneless</group>
+    ///                 <group></group>
+    ///             </groups>
+    ///             <segments>
+    ///                 <segment bytes="739067" number="1">9cacde4c986547369becbf97003fb2c5-9483514693959@example</segment>
+    ///             </segments>
+    ///         </file>
+    ///     </nzb>
+    ///     "#;
+    /// match Nzb::parse_strict(xml) {
+    ///     Err(ParseNzbError::Diagnostic(diagnostic)) => assert_eq!(diagnostic.kind(), DiagnosticKind::EmptyGroup),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn parse_strict(nzb: impl AsRef<str>) -> Result<Self, ParseNzbError> {
+        Self::parse_with_diagnostics(nzb.as_ref(), true).map(|(nzb, _)| nzb)
+    }
+
+    fn parse_with_diagnostics(nzb: &str, strict: bool) -> Result<(Self, Vec<Diagnostic>), ParseNzbError> {
+        let nzb = xml::parse_document(nzb)?;
+        let meta = Meta::parse(nzb.roxmltree());
+        let (files, diagnostics) = parse_files(&nzb, strict)?;
+        Ok((Self::new(meta, files, None), diagnostics))
+    }
+
+    /// Parses raw bytes into an [`Nzb`] instance.
+    /// Handles both regular and compressed NZBs, detecting the compression format by its magic bytes.
+    ///
+    /// Gzip is always supported. Bzip2, Xz, and Zstandard require the
+    /// `bzip2`, `xz`, and `zstd` features respectively.
+    ///
+    /// The character encoding is detected from the byte order mark or the
+    /// `encoding` in the XML declaration, and defaults to UTF-8, so NZBs in
+    /// legacy encodings such as ISO-8859-1, Windows-1252, or UTF-16 are
+    /// transcoded before parsing.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`ParseNzbReaderError`] in the following cases:
+    /// - If the bytes look compressed but cannot be decompressed.
+    /// - If the bytes are not valid in their detected encoding.
+    /// - If the declared encoding is not supported ([`ParseNzbError::UnsupportedEncoding`]).
+    /// - If the contents are malformed and cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbReaderError};
+    ///
+    /// fn main() -> Result<(), ParseNzbReaderError> {
+    ///     let bytes = std::fs::read("tests/nzbs/big_buck_bunny.nzb.gz").unwrap();
+    ///     let nzb = Nzb::from_bytes(bytes)?;
+    ///     assert_eq!(nzb.file().name(), Some("Big Buck Bunny - S01E01.mkv"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_bytes(nzb: impl AsRef<[u8]>) -> Result<Self, ParseNzbReaderError> {
+        let content = input::decompress(nzb.as_ref(), None)?;
+        let content = input::decode(content)?;
+        Ok(Self::parse(content)?)
+    }
+
+    /// Reads everything from `reader` and parses it into an [`Nzb`] instance.
+    /// Handles both regular and compressed NZBs, the same way as [`Nzb::from_bytes`].
+    ///
+    /// For very large NZBs, consider [`NzbReader`](crate::NzbReader) instead,
+    /// which does not need to hold the entire document in memory.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`ParseNzbReaderError`] in the following cases:
+    /// - If reading from `reader` fails.
+    /// - If the contents look compressed but cannot be decompressed.
+    /// - If the contents are not valid in their detected encoding.
+    /// - If the declared encoding is not supported ([`ParseNzbError::UnsupportedEncoding`]).
+    /// - If the contents are malformed and cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbReaderError};
+    ///
+    /// fn main() -> Result<(), ParseNzbReaderError> {
+    ///     let file = std::fs::File::open("tests/nzbs/big_buck_bunny.nzb").unwrap();
+    ///     let nzb = Nzb::from_reader(file)?;
+    ///     assert_eq!(nzb.file().name(), Some("Big Buck Bunny - S01E01.mkv"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, ParseNzbReaderError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|source| ParseNzbReaderError::Io { source })?;
+        Self::from_bytes(bytes)
+    }
+
     /// Parse a file into an [`Nzb`] instance.
-    /// Handles both regular and gzipped NZB files.
+    /// Handles both regular and compressed NZB files.
+    ///
+    /// A file is decompressed if it has the extension of a supported compression format
+    /// (`.gz`, `.bz2`, `.xz`, or `.zst`), or if its contents start with the magic bytes of one.
+    /// See [`Nzb::from_bytes`] for which formats are supported.
+    /// For zip archives, use `Nzb::parse_zip_file` (requires the `zip` feature) instead.
+    /// The character encoding is detected the same way as in [`Nzb::from_bytes`].
     ///
     /// # Errors
     ///
     /// This function returns an [`ParseNzbFileError`] in the following cases:
     /// - If the file cannot be read.
+    /// - If the file looks compressed but cannot be decompressed.
+    /// - If the contents of the file are not valid in their detected encoding.
+    /// - If the declared encoding is not supported ([`ParseNzbError::UnsupportedEncoding`]).
     /// - If the contents of the file are malformed and cannot be parsed.
     ///
     /// # Example
@@ -95,36 +324,308 @@ impl Nzb {
     /// ```
     pub fn parse_file(nzb: impl AsRef<Path>) -> Result<Self, ParseNzbFileError> {
         let file = nzb.as_ref();
+        let codec = file.extension().and_then(Codec::from_extension);
 
-        let content = if file.extension().is_some_and(|f| f.eq_ignore_ascii_case("gz")) {
-            let gzipped = fs::read(file).map_err(|source| ParseNzbFileError::from_gzip_err(source, file))?;
-            let mut decoder = GzDecoder::new(&gzipped[..]);
-            let mut content = String::with_capacity(gzipped.len());
-            decoder
-                .read_to_string(&mut content)
-                .map_err(|source| ParseNzbFileError::from_gzip_err(source, file))?;
-            content
-        } else {
-            fs::read_to_string(file).map_err(|source| ParseNzbFileError::from_io_err(source, file))?
-        };
+        let bytes = fs::read(file).map_err(|source| ParseNzbFileError::from_io_err(source, file))?;
+        let content =
+            input::decompress(&bytes, codec).map_err(|error| ParseNzbFileError::from_decompress_err(error, file))?;
+        let content = input::decode(content)?;
 
-        Ok(Self::parse(content)?)
+        let mut nzb = Self::parse(content)?;
+        nzb.source_name = file.file_name().map(|name| name.to_string_lossy().into_owned());
+        Ok(nzb)
+    }
+
+    /// Parse a file into an [`Nzb`] instance, the same way as [`Nzb::parse_file`], and
+    /// collect the passwords embedded in its file name and title into [`Meta::passwords`].
+    ///
+    /// Passwords are embedded as `Name{{password}}.nzb` or `Name / password.nzb`, see [`Meta::split_password`].
+    /// The password is removed from the title, and the file name is returned without it and its extension,
+    /// e.g., `Name`, as the name of the job. Passwords that are already present are not added again.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbFileError`] as [`Nzb::parse_file`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use nzb_rs::{Nzb, ParseNzbFileError};
+    ///
+    /// fn main() -> Result<(), ParseNzbFileError> {
+    ///     let (nzb, name) = Nzb::parse_file_with_passwords("Big Buck Bunny{{secret}}.nzb")?;
+    ///     assert_eq!(name, "Big Buck Bunny");
+    ///     assert!(nzb.meta().passwords().iter().any(|password| password == "secret"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn parse_file_with_passwords(nzb: impl AsRef<Path>) -> Result<(Self, String), ParseNzbFileError> {
+        let mut nzb = Self::parse_file(nzb)?;
+        let stem = nzb.source_name.as_deref().map(input::nzb_stem).unwrap_or_default();
+        let (name, password) = Meta::split_password(stem);
+        let (name, password) = (name.to_owned(), password.map(str::to_owned));
+
+        if let Some(password) = password {
+            nzb.meta.add_password(&password);
+        }
+        nzb.meta.extract_title_password();
+        Ok((nzb, name))
     }
+
+    /// Parses every NZB inside a zip archive read from `reader`, keyed by entry name.
+    ///
+    /// Entries with an `.nzb` extension are parsed the same way as [`Nzb::from_bytes`],
+    /// so they may themselves be compressed (e.g., `release.nzb.gz`). Every other entry is ignored,
+    /// and an archive without any NZBs results in an empty map.
+    ///
+    /// This function requires the `zip` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`ParseNzbReaderError`] in the following cases:
+    /// - If `reader` is not a valid zip archive, or an entry cannot be extracted ([`ParseNzbReaderError::Zip`]).
+    /// - If any NZB inside the archive cannot be parsed ([`ParseNzbReaderError::ZipEntry`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbReaderError};
+    ///
+    /// fn main() -> Result<(), ParseNzbReaderError> {
+    ///     let file = std::fs::File::open("tests/nzbs/archive.zip").unwrap();
+    ///     let nzbs = Nzb::from_zip_reader(file)?;
+    ///     assert_eq!(nzbs["big_buck_bunny.nzb"].file().name(), Some("Big Buck Bunny - S01E01.mkv"));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn from_zip_reader(reader: impl Read + Seek) -> Result<BTreeMap<String, Self>, ParseNzbReaderError> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+        let mut nzbs = BTreeMap::new();
+
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            if !entry.is_file() || !input::is_nzb_entry(entry.name()) {
+                continue;
+            }
+
+            let name = entry.name().to_string();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(zip::result::ZipError::from)?;
+
+            let mut nzb = Self::from_bytes(bytes).map_err(|source| ParseNzbReaderError::ZipEntry {
+                source: Box::new(source),
+                entry: name.clone(),
+            })?;
+            nzb.source_name = name.rsplit('/').next().map(str::to_string);
+            nzbs.insert(name, nzb);
+        }
+
+        Ok(nzbs)
+    }
+
+    /// Parses every NZB inside a zip archive, keyed by entry name.
+    ///
+    /// See [`Nzb::from_zip_reader`] for how entries are handled.
+    ///
+    /// This function requires the `zip` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`ParseNzbFileError`] in the following cases:
+    /// - If the file cannot be read.
+    /// - If the file is not a valid zip archive, or an entry cannot be extracted ([`ParseNzbFileError::Zip`]).
+    /// - If any NZB inside the archive cannot be parsed ([`ParseNzbFileError::ZipEntry`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbFileError};
+    ///
+    /// fn main() -> Result<(), ParseNzbFileError> {
+    ///     let nzbs = Nzb::parse_zip_file("tests/nzbs/archive.zip")?;
+    ///     assert_eq!(nzbs.len(), 2);
+    ///     assert_eq!(nzbs["spec_example.nzb.gz"].meta().title(), Some("Your File!"));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn parse_zip_file(nzb: impl AsRef<Path>) -> Result<BTreeMap<String, Self>, ParseNzbFileError> {
+        let file = nzb.as_ref();
+        let reader = fs::File::open(file).map_err(|source| ParseNzbFileError::from_io_err(source, file))?;
+        Self::from_zip_reader(io::BufReader::new(reader))
+            .map_err(|error| ParseNzbFileError::from_reader_err(error, file))
+    }
+
+    /// Merges several NZBs, such as the parts of a season pack or a repost of the same release, into one.
+    ///
+    /// Files that share a segment, or else have the same [`File::name`] (or the same subject if they have
+    /// no name), are merged into a single file, with the union of their groups and segments, where segments
+    /// with the same Message-ID are only kept once. The metadata is merged as well:
+    /// passwords and tags are combined, while the first title and category win.
+    /// Only the [`Nzb::source_name`] of the first NZB is kept, even if it has none.
+    /// The returned [`MergeReport`] tells what was deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the same [`ParseNzbError`] as [`NzbBuilder::build`]
+    /// if the merged files do not make up a valid NZB, e.g., [`ParseNzbError::FileElement`] if there are no NZBs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbFileError};
+    ///
+    /// fn main() -> Result<(), ParseNzbFileError> {
+    ///     let nzb = Nzb::parse_file("tests/nzbs/big_buck_bunny.nzb")?;
+    ///     let (merged, report) = Nzb::merge([nzb.clone(), nzb.clone()])?;
+    ///     assert_eq!(merged, nzb);
+    ///     assert_eq!(report.duplicate_files().len(), nzb.files().len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn merge(nzbs: impl IntoIterator<Item = Nzb>) -> Result<(Self, MergeReport), ParseNzbError> {
+        merge::merge(nzbs)
+    }
+
+    /// Serializes the [`Nzb`] into a spec compliant NZB document.
+    ///
+    /// The output includes the XML declaration, the NZB 1.1 DOCTYPE and
+    /// the `http://www.newzbin.com/DTD/2003/nzb` namespace, and can be
+    /// parsed back with [`Nzb::parse`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbFileError};
+    ///
+    /// fn main() -> Result<(), ParseNzbFileError> {
+    ///     let nzb = Nzb::parse_file("tests/nzbs/spec_example.nzb")?;
+    ///     let xml = nzb.to_xml();
+    ///     assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    ///     assert_eq!(Nzb::parse(xml)?, nzb);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn to_xml(&self) -> String {
+        let mut out = Vec::new();
+        // Writing into a `Vec<u8>` cannot fail, and everything written
+        // originates from `&str` values, so the output is always valid UTF-8.
+        writer::write_nzb(self, &mut out).expect("writing to a Vec should never fail");
+        String::from_utf8(out).expect("serialized NZB should be valid UTF-8")
+    }
+
+    /// Serializes the [`Nzb`] into a spec compliant NZB document
+    /// and writes it to `writer`.
+    ///
+    /// See [`Nzb::to_xml`] for details on the output format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing to `writer` fails.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer::write_nzb(self, &mut writer)?;
+        writer.flush()
+    }
+
+    /// Write the [`Nzb`] to a file.
+    /// Writes a gzipped NZB if the path has a `.gz` extension, and a regular NZB otherwise.
+    ///
+    /// Gzipped output uses the default compression level;
+    /// see [`Nzb::write_file_with_level`] to choose a different one.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`WriteNzbFileError`] in the following cases:
+    /// - If the file cannot be created or written to.
+    /// - If the gzip compression of the output fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use nzb_rs::Nzb;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let nzb = Nzb::parse_file("tests/nzbs/big_buck_bunny.nzb")?;
+    ///     nzb.write_file("big_buck_bunny.nzb.gz")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_file(&self, nzb: impl AsRef<Path>) -> Result<(), WriteNzbFileError> {
+        self.write_file_with_compression(nzb.as_ref(), Compression::default())
+    }
+
+    /// Write the [`Nzb`] to a file, using the given gzip compression `level`.
+    ///
+    /// Like [`Nzb::write_file`], the output is only gzipped if the path has a `.gz`
+    /// extension, otherwise `level` is ignored. The level ranges from `0` (no compression)
+    /// to `9` (best compression), larger values are clamped to `9`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`WriteNzbFileError`] in the following cases:
+    /// - If the file cannot be created or written to.
+    /// - If the gzip compression of the output fails.
+    pub fn write_file_with_level(&self, nzb: impl AsRef<Path>, level: u32) -> Result<(), WriteNzbFileError> {
+        self.write_file_with_compression(nzb.as_ref(), Compression::new(level.min(9)))
+    }
+
+    fn write_file_with_compression(&self, file: &Path, compression: Compression) -> Result<(), WriteNzbFileError> {
+        let output = fs::File::create(file).map_err(|source| WriteNzbFileError::from_io_err(source, file))?;
+        let output = BufWriter::new(output);
+
+        if file.extension().is_some_and(|f| f.eq_ignore_ascii_case("gz")) {
+            let mut encoder = GzEncoder::new(output, compression);
+            writer::write_nzb(self, &mut encoder).map_err(|source| WriteNzbFileError::from_gzip_err(source, file))?;
+            encoder
+                .finish()
+                .and_then(|mut output| output.flush())
+                .map_err(|source| WriteNzbFileError::from_gzip_err(source, file))
+        } else {
+            self.write_to(output)
+                .map_err(|source| WriteNzbFileError::from_io_err(source, file))
+        }
+    }
+
     /// Optional creator-definable metadata for the contents of the NZB.
     #[must_use]
     pub fn meta(&self) -> &Meta {
         &self.meta
     }
 
+    /// Optional creator-definable metadata for the contents of the NZB, for editing it in place.
+    ///
+    /// To edit the files, convert the [`Nzb`] into an [`NzbBuilder`] instead, see [`NzbBuilder::files_mut`].
+    pub fn meta_mut(&mut self) -> &mut Meta {
+        &mut self.meta
+    }
+
     /// File objects representing the files included in the NZB.
     #[must_use]
     pub fn files(&self) -> &[File] {
         &self.files
     }
 
+    /// Name of the file the NZB was parsed from, e.g., `movie.nzb.gz`.
+    ///
+    /// This is only known for NZBs parsed with [`Nzb::parse_file`], or from a zip archive,
+    /// and is not taken into account when comparing NZBs. It is kept when the NZB is edited
+    /// through an [`NzbBuilder`], and a merged NZB keeps the one of the first NZB, see [`Nzb::merge`].
+    #[must_use]
+    pub fn source_name(&self) -> Option<&str> {
+        self.source_name.as_deref()
+    }
+
     /// The main content file (episode, movie, etc) in the NZB.
     /// This is determined by finding the largest non `par2` file in the NZB
-    /// and may not always be accurate.
+    /// and may not always be accurate. See [`Nzb::main_file`] for an alternative
+    /// that understands archives and samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the NZB only contains `.par2` files, which cannot happen for a parsed or built NZB,
+    /// but can for one deserialized with the `serde` feature.
     #[must_use]
     pub fn file(&self) -> &File {
         // self.files is guaranteed to have at least one file.
@@ -135,6 +636,125 @@ impl Nzb {
             .expect("NZB should have at least one non-`.par2` file")
     }
 
+    /// The set of files that make up the main content (episode, movie, etc) of the NZB.
+    ///
+    /// This is the set with the largest total size of data files, ignoring samples, proofs and
+    /// bonus material such as trailers, unless there is nothing else. Since volumes are grouped into sets,
+    /// see [`Nzb::file_sets`], archived releases are judged by the size of the whole archive.
+    ///
+    /// Returns [`None`] if the NZB only contains `.par2` files.
+    #[must_use]
+    pub fn main_set(&self) -> Option<FileSet<'_>> {
+        let sets = self
+            .file_sets()
+            .into_iter()
+            .filter(|set| !set.files().is_empty())
+            .collect::<Vec<_>>();
+        let is_content = |set: &FileSet<'_>| {
+            !subject::is_sample(set.name()) && !subject::is_proof(set.name()) && !subject::is_extra(set.name())
+        };
+        let prefer_content = sets.iter().any(is_content);
+
+        sets.into_iter()
+            .filter(|set| !prefer_content || is_content(set))
+            .max_by_key(FileSet::size)
+    }
+
+    /// The main content file (episode, movie, etc) in the NZB.
+    ///
+    /// This is the first volume of the archive in [`Nzb::main_set`] for archived releases,
+    /// and its largest file otherwise. Unlike [`Nzb::file`], this does not panic.
+    ///
+    /// Returns [`None`] if the NZB only contains `.par2` files.
+    #[must_use]
+    pub fn main_file(&self) -> Option<&File> {
+        let set = self.main_set()?;
+        set.rar_volumes()
+            .first()
+            .copied()
+            .or_else(|| set.files().iter().copied().max_by_key(|f| f.size()))
+    }
+
+    /// Release information parsed from the title in the metadata or, failing that,
+    /// from the name of the [`Nzb::main_set`], see [`ReleaseInfo`].
+    ///
+    /// Returns [`None`] if there is no title and the main set is missing or obfuscated.
+    #[cfg(feature = "release")]
+    #[must_use]
+    pub fn release_info(&self) -> Option<ReleaseInfo> {
+        if let Some(title) = self.meta.title() {
+            return Some(ReleaseInfo::parse(title));
+        }
+
+        let set = self.main_set()?;
+        if set.files().iter().any(|f| f.is_obfuscated()) {
+            return None;
+        }
+        Some(ReleaseInfo::parse(set_stem(set.name())))
+    }
+
+    /// Human-readable name for the NZB, e.g., for showing it in a queue, even if its files are obfuscated.
+    ///
+    /// This is the first one available of:
+    /// 1. The title in the metadata, see [`Meta::title`].
+    /// 2. The most common text before the filename in the subjects that is not obfuscated, see [`Subject::prefix`].
+    /// 3. The most common name of a file set among the files that are not obfuscated, e.g., `movie` for
+    ///    `movie.part01.rar` and `movie.vol00+01.par2`.
+    /// 4. The name of the file the NZB was parsed from, without its extension or an embedded password,
+    ///    see [`Nzb::source_name`] and [`Meta::split_password`].
+    ///
+    /// Returns [`None`] if none of these are available.
+    ///
+    /// [`Subject::prefix`]: crate::Subject::prefix
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nzb_rs::{Nzb, ParseNzbFileError};
+    ///
+    /// fn main() -> Result<(), ParseNzbFileError> {
+    ///     let nzb = Nzb::parse_file("tests/nzbs/big_buck_bunny.nzb")?;
+    ///     assert_eq!(nzb.display_name(), Some("Big Buck Bunny - S01E01"));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn display_name(&self) -> Option<&str> {
+        if let Some(title) = self.meta.title() {
+            return Some(title);
+        }
+
+        let prefixes = self
+            .files
+            .iter()
+            .filter_map(|f| f.parsed_subject().prefix())
+            .filter(|prefix| !subject::is_obfuscated(prefix));
+        if let Some(prefix) = most_common(prefixes) {
+            return Some(prefix);
+        }
+
+        let stems = self
+            .files
+            .iter()
+            .filter(|f| !f.is_obfuscated())
+            .filter_map(File::name)
+            .map(|name| set_stem(subject::set_name(name)));
+        most_common(stems).or_else(|| {
+            let stem = input::nzb_stem(self.source_name.as_deref()?);
+            Some(Meta::split_password(stem).0)
+        })
+    }
+
+    /// Files in the NZB that make up its content, i.e., every file that is not a sample,
+    /// a proof, an extra, or a `.par2` file.
+    ///
+    /// See [`File::is_sample`], [`File::is_proof`] and [`File::is_extra`].
+    pub fn content_files(&self) -> impl Iterator<Item = &File> {
+        self.files
+            .iter()
+            .filter(|f| !f.is_par2() && !f.is_sample() && !f.is_proof() && !f.is_extra())
+    }
+
     /// Total size of all the files in the NZB.
     #[must_use]
     pub fn size(&self) -> u64 {
@@ -173,6 +793,61 @@ impl Nzb {
         self.files.iter().filter(|f| f.is_par2())
     }
 
+    /// Files in the NZB grouped into sets that can be downloaded and repaired independently,
+    /// such as the main RAR set, a sample RAR set, and subtitles, each with their own `.par2` files.
+    ///
+    /// Sets are ordered by their first file in [`Nzb::files`], and so are the files within each set.
+    /// Files whose name cannot be extracted each form a set of their own.
+    /// See [`FileSet`] for how files are grouped.
+    #[must_use]
+    pub fn file_sets(&self) -> Vec<FileSet<'_>> {
+        let mut sets: Vec<FileSet<'_>> = Vec::new();
+        // Index in `sets` of each set, by its name in lowercase.
+        let mut by_name = HashMap::new();
+
+        for file in &self.files {
+            let name = file.name().map_or(file.subject(), subject::set_name);
+            let index = *by_name.entry(name.to_ascii_lowercase()).or_insert_with(|| {
+                sets.push(FileSet::new(name));
+                sets.len() - 1
+            });
+            sets[index].push(file);
+        }
+
+        sets
+    }
+
+    /// `.rar` files in the NZB in extraction order, i.e., sorted by the name of their archive
+    /// and then by their volume number, see [`File::rar_volume`].
+    ///
+    /// Use [`FileSet::missing_rar_volumes`] to detect missing volumes.
+    pub fn rar_files(&self) -> impl Iterator<Item = &File> {
+        let mut files = self.files.iter().filter(|f| f.is_rar()).collect::<Vec<_>>();
+        files.sort_by_cached_key(|f| {
+            f.rar_volume()
+                .map(|volume| (volume.base().to_lowercase(), volume.number()))
+        });
+        files.into_iter()
+    }
+
+    /// Files in the NZB of the specified kind, see [`File::kind`].
+    pub fn files_of_kind(&self, kind: FileKind) -> impl Iterator<Item = &File> {
+        self.files.iter().filter(move |f| f.kind() == kind)
+    }
+
+    /// Return [`true`] if any file in the NZB is of the specified kind, [`false`] otherwise.
+    #[must_use]
+    pub fn has_kind(&self, kind: FileKind) -> bool {
+        self.files.iter().any(|f| f.kind() == kind)
+    }
+
+    /// Return [`true`] if any file in the NZB is an executable, such as an `.exe`, `.scr` or `.lnk`,
+    /// [`false`] otherwise. NZBs with such payloads are likely malware.
+    #[must_use]
+    pub fn is_suspicious(&self) -> bool {
+        self.has_kind(FileKind::Executable)
+    }
+
     /// Total size of all the `.par2` files.
     #[must_use]
     pub fn par2_size(&self) -> u64 {
@@ -219,4 +894,41 @@ impl Nzb {
     pub fn is_obfuscated(&self) -> bool {
         self.files.iter().any(File::is_obfuscated)
     }
+
+    /// Return [`true`] if no segment is missing from any file in the NZB, [`false`] otherwise.
+    ///
+    /// See [`File::is_complete`].
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.files.iter().all(File::is_complete)
+    }
+
+    /// Files in the NZB that are missing at least one segment.
+    ///
+    /// See [`File::missing_segments`].
+    pub fn incomplete_files(&self) -> impl Iterator<Item = &File> {
+        self.files.iter().filter(|f| !f.is_complete())
+    }
+}
+
+/// Stem of the name of a [`FileSet`]. A set of plain files is named after the whole file, e.g., `movie.mkv`,
+/// while a set of archive volumes is already named without an extension, e.g., `movie` for `movie.part01.rar`.
+fn set_stem(name: &str) -> &str {
+    match FileKind::from_name(name) {
+        FileKind::Other => name,
+        _ => subject::file_stem(name),
+    }
+}
+
+/// The name that occurs most often, preferring the one seen first on a tie.
+fn most_common<'a>(names: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    // The count of each name, and where it was first seen.
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (position, name) in names.enumerate() {
+        counts.entry(name).or_insert((0, position)).0 += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(_, (count, position))| (count, Reverse(position)))
+        .map(|(name, _)| name)
 }
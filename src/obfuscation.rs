@@ -0,0 +1,421 @@
+use std::sync::{Arc, LazyLock};
+
+use crate::file::File;
+use crate::subject;
+
+/// The rule that decided whether a file name is obfuscated, see [`ObfuscationAnalysis`].
+///
+/// The built-in rules are those of SABnzbd's [`is_probably_obfuscated`][0],
+/// plus a few optional ones that can be enabled on an [`ObfuscationDetector`].
+///
+/// [0]: <https://github.com/sabnzbd/sabnzbd/blob/d21a1119932896c1f7fea1b804e99c70f05dbd19/sabnzbd/deobfuscate_filenames.py#L103>
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObfuscationRule {
+    /// Obfuscated: the name could not be extracted from the subject.
+    MissingName,
+    /// Obfuscated: a 32-character hex string, e.g., `b082fa0beaa644d3aa01045d5b8d0b36`.
+    Hex32,
+    /// Obfuscated: a long string of hex digits and dots, e.g., `0675e29e9abfd2.f7d069dab0b853283cc1b069a25f82.6547`.
+    HexAndDots,
+    /// Obfuscated: a long run of hex digits along with bracketed words, e.g., `[BlaBla] 5937bc5e32146e... [Brrr]`.
+    BracketedHex,
+    /// Obfuscated: starts with `abc.xyz`, e.g., `abc.xyz.a4c567edbcbf27.BLA`.
+    AbcXyz,
+    /// Clear: upper and lower case letters separated by spaces or dots, e.g., `Great Distro`.
+    MixedCaseWords,
+    /// Clear: several words separated by spaces or dots, e.g., `this is a download`.
+    SeparatedWords,
+    /// Clear: letters and a year-like number separated by spaces or dots, e.g., `Beast 2020`.
+    WordsAndNumber,
+    /// Clear: a capitalized word in mostly lower case, e.g., `Catullus`.
+    Capitalized,
+    /// Clear: enough letters outside of ASCII, e.g., `ヴァイオレット・エヴァーガーデン`,
+    /// see [`ObfuscationDetector::non_ascii_titles`].
+    NonAsciiLetters,
+    /// Clear: upper case letters only, e.g., `DUNE`, see [`ObfuscationDetector::all_caps_titles`].
+    AllCaps,
+    /// A custom rule with the given name, see [`ObfuscationDetector::rule`].
+    Custom(String),
+    /// Obfuscated: no other rule applied.
+    Fallback,
+}
+
+impl std::fmt::Display for ObfuscationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingName => write!(f, "missing name"),
+            Self::Hex32 => write!(f, "32-character hex string"),
+            Self::HexAndDots => write!(f, "hex digits and dots"),
+            Self::BracketedHex => write!(f, "bracketed words with hex digits"),
+            Self::AbcXyz => write!(f, "starts with 'abc.xyz'"),
+            Self::MixedCaseWords => write!(f, "mixed case words"),
+            Self::SeparatedWords => write!(f, "separated words"),
+            Self::WordsAndNumber => write!(f, "words and a number"),
+            Self::Capitalized => write!(f, "capitalized word"),
+            Self::NonAsciiLetters => write!(f, "non-ASCII letters"),
+            Self::AllCaps => write!(f, "all caps"),
+            Self::Custom(name) => write!(f, "custom rule '{name}'"),
+            Self::Fallback => write!(f, "fallback"),
+        }
+    }
+}
+
+/// The outcome of checking whether a file name is obfuscated, along with the reason for it.
+///
+/// See [`File::obfuscation`] and [`ObfuscationDetector::analyze`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObfuscationAnalysis {
+    obfuscated: bool,
+    rule: ObfuscationRule,
+    confidence: f32,
+}
+
+impl ObfuscationAnalysis {
+    fn new(obfuscated: bool, rule: ObfuscationRule, confidence: f32) -> Self {
+        Self {
+            obfuscated,
+            rule,
+            confidence,
+        }
+    }
+
+    /// Return [`true`] if the name is considered obfuscated, [`false`] otherwise.
+    #[must_use]
+    pub fn is_obfuscated(&self) -> bool {
+        self.obfuscated
+    }
+
+    /// The rule that decided the outcome.
+    #[must_use]
+    pub fn rule(&self) -> &ObfuscationRule {
+        &self.rule
+    }
+
+    /// How certain the outcome is, from `0.0` to `1.0`.
+    ///
+    /// The patterns that are certainly obfuscated, such as [`ObfuscationRule::Hex32`], have a confidence
+    /// of `1.0`, while [`ObfuscationRule::Fallback`] only has a confidence of `0.5`.
+    #[must_use]
+    pub fn confidence(&self) -> f32 {
+        self.confidence
+    }
+}
+
+type CustomRule = Arc<dyn Fn(&str) -> Option<bool> + Send + Sync>;
+
+/// Decides whether file names are obfuscated, see [`ObfuscationAnalysis`].
+///
+/// The default detector matches SABnzbd's [`is_probably_obfuscated`][0], and is the one used by
+/// [`File::is_obfuscated`]. The thresholds of its rules can be tuned, additional rules can be
+/// enabled for names it gets wrong, and custom rules can be added, which take precedence.
+///
+/// # Example
+///
+/// ```
+/// use nzb_rs::{ObfuscationDetector, ObfuscationRule};
+///
+/// let detector = ObfuscationDetector::new()
+///     .non_ascii_titles(true)
+///     .all_caps_titles(4)
+///     .rule("release group", 1.0, |stem| stem.ends_with("-GROUP").then_some(false));
+///
+/// assert!(!detector.analyze("ヴァイオレット・エヴァーガーデン").is_obfuscated());
+/// assert_eq!(detector.analyze("DUNE").rule(), &ObfuscationRule::AllCaps);
+/// assert_eq!(
+///     detector.analyze("xyz-GROUP").rule(),
+///     &ObfuscationRule::Custom("release group".to_owned())
+/// );
+/// assert_eq!(detector.analyze("b082fa0beaa644d3aa01045d5b8d0b36").confidence(), 1.0);
+/// ```
+///
+/// [0]: <https://github.com/sabnzbd/sabnzbd/blob/d21a1119932896c1f7fea1b804e99c70f05dbd19/sabnzbd/deobfuscate_filenames.py#L103>
+#[derive(Clone)]
+pub struct ObfuscationDetector {
+    hex_run_len: usize,
+    hex_and_dots_min_len: usize,
+    non_ascii_titles: bool,
+    all_caps_min_len: Option<usize>,
+    rules: Vec<(String, f32, CustomRule)>,
+}
+
+impl Default for ObfuscationDetector {
+    fn default() -> Self {
+        Self {
+            hex_run_len: 30,
+            hex_and_dots_min_len: 40,
+            non_ascii_titles: false,
+            all_caps_min_len: None,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ObfuscationDetector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObfuscationDetector")
+            .field("hex_run_len", &self.hex_run_len)
+            .field("hex_and_dots_min_len", &self.hex_and_dots_min_len)
+            .field("non_ascii_titles", &self.non_ascii_titles)
+            .field("all_caps_min_len", &self.all_caps_min_len)
+            .field("rules", &self.rules.iter().map(|(name, ..)| name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The default detector, shared by [`subject::is_obfuscated`].
+pub(crate) static DEFAULT_DETECTOR: LazyLock<ObfuscationDetector> = LazyLock::new(ObfuscationDetector::default);
+
+impl ObfuscationDetector {
+    /// Creates a new [`ObfuscationDetector`] that matches SABnzbd's `is_probably_obfuscated`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of consecutive hex digits that, along with two bracketed words,
+    /// make a name obfuscated, see [`ObfuscationRule::BracketedHex`]. Defaults to `30`.
+    #[must_use]
+    pub fn hex_run_len(mut self, len: usize) -> Self {
+        self.hex_run_len = len;
+        self
+    }
+
+    /// Sets the minimum length of a name consisting only of hex digits and dots
+    /// to be obfuscated, see [`ObfuscationRule::HexAndDots`]. Defaults to `40`.
+    #[must_use]
+    pub fn hex_and_dots_min_len(mut self, len: usize) -> Self {
+        self.hex_and_dots_min_len = len;
+        self
+    }
+
+    /// Treats names with at least two letters outside of ASCII, such as Japanese titles,
+    /// as clear, see [`ObfuscationRule::NonAsciiLetters`]. Disabled by default, since such
+    /// letters have no case, which makes the default rules consider them obfuscated.
+    #[must_use]
+    pub fn non_ascii_titles(mut self, enabled: bool) -> Self {
+        self.non_ascii_titles = enabled;
+        self
+    }
+
+    /// Treats names with at least `min_len` letters, all of them upper case, as clear,
+    /// e.g., `DUNE` for a `min_len` of `4`, see [`ObfuscationRule::AllCaps`]. Disabled by default.
+    #[must_use]
+    pub fn all_caps_titles(mut self, min_len: usize) -> Self {
+        self.all_caps_min_len = Some(min_len);
+        self
+    }
+
+    /// Adds a custom rule, which returns `Some(true)` for an obfuscated name, `Some(false)`
+    /// for a clear one, or `None` to defer to the next rule.
+    ///
+    /// Custom rules are checked before the built-in ones, in the order they were added,
+    /// and are reported as [`ObfuscationRule::Custom`] with the given `name` and `confidence`,
+    /// which is clamped to `0.0..=1.0`, or `0.0` if it is not a number.
+    #[must_use]
+    pub fn rule(
+        mut self,
+        name: impl Into<String>,
+        confidence: f32,
+        rule: impl Fn(&str) -> Option<bool> + Send + Sync + 'static,
+    ) -> Self {
+        let confidence = if confidence.is_nan() {
+            0.0
+        } else {
+            confidence.clamp(0.0, 1.0)
+        };
+        self.rules.push((name.into(), confidence, Arc::new(rule)));
+        self
+    }
+
+    /// Analyzes the name of a [`File`], see [`ObfuscationDetector::analyze`].
+    ///
+    /// A file whose name cannot be extracted is considered obfuscated.
+    #[must_use]
+    pub fn analyze_file(&self, file: &File) -> ObfuscationAnalysis {
+        match file.stem() {
+            Some(stem) => self.analyze(stem),
+            None => ObfuscationAnalysis::new(true, ObfuscationRule::MissingName, 0.5),
+        }
+    }
+
+    /// Analyzes whether a file stem (the name without its extension) is obfuscated.
+    ///
+    /// Unlike SABnzbd's `is_probably_obfuscated`, this does not handle paths or extensions,
+    /// so passing anything else will yield incorrect results.
+    #[must_use]
+    pub fn analyze(&self, filestem: &str) -> ObfuscationAnalysis {
+        for (name, confidence, rule) in &self.rules {
+            if let Some(obfuscated) = rule(filestem) {
+                return ObfuscationAnalysis::new(obfuscated, ObfuscationRule::Custom(name.clone()), *confidence);
+            }
+        }
+
+        let obfuscated = |rule| ObfuscationAnalysis::new(true, rule, 1.0);
+        let clear = |rule, confidence| ObfuscationAnalysis::new(false, rule, confidence);
+
+        // In a lot of cases, we do not care about anything other than ASCII characters.
+        // So, we can work on the byte level for minor performance gains.
+        let filestem_bytes = filestem.as_bytes();
+        let length = filestem_bytes.len();
+
+        // First, the patterns that are certainly obfuscated:
+
+        // 32-character hex strings, e.g.
+        // ...blabla.H.264/b082fa0beaa644d3aa01045d5b8d0b36.mkv is certainly obfuscated
+        if length == 32 && filestem_bytes.iter().all(u8::is_ascii_hexdigit) {
+            return obfuscated(ObfuscationRule::Hex32);
+        }
+
+        // 40+ chars consisting only of hex digits and dots, e.g.
+        // 0675e29e9abfd2.f7d069dab0b853283cc1b069a25f82.6547
+        if length >= self.hex_and_dots_min_len && filestem_bytes.iter().all(|b| b.is_ascii_hexdigit() || *b == b'.') {
+            return obfuscated(ObfuscationRule::HexAndDots);
+        }
+
+        // "[BlaBla] something [More] something 5937bc5e32146e.bef89a622e4a23f07b0d3757ad5e8a.a02b264e [Brrr]"
+        // So: square brackets plus 30+ hex digit
+        if subject::has_consecutive_hexdigits(filestem, self.hex_run_len) && subject::has_two_bracketed_words(filestem)
+        {
+            return obfuscated(ObfuscationRule::BracketedHex);
+        }
+
+        // /some/thing/abc.xyz.a4c567edbcbf27.BLA is certainly obfuscated
+        if filestem_bytes.starts_with(b"abc.xyz") {
+            return obfuscated(ObfuscationRule::AbcXyz);
+        }
+
+        // Then, patterns that are not obfuscated but typical, clear names:
+
+        // these are signals for the obfuscation versus non-obfuscation
+        let mut decimals: u32 = 0;
+        let mut upperchars: u32 = 0;
+        let mut lowerchars: u32 = 0;
+        let mut spacesdots: u32 = 0;
+        let mut non_ascii_letters: u32 = 0;
+
+        for char in filestem.chars() {
+            if char.is_ascii_digit() {
+                decimals += 1;
+            }
+            if char.is_uppercase() {
+                upperchars += 1;
+            }
+            if char.is_lowercase() {
+                lowerchars += 1;
+            }
+            if char == ' ' || char == '.' || char == '_' {
+                spacesdots += 1;
+            }
+            if !char.is_ascii() && char.is_alphabetic() {
+                non_ascii_letters += 1;
+            }
+        }
+
+        // Example: "Great Distro"
+        if upperchars >= 2 && lowerchars >= 2 && spacesdots >= 1 {
+            return clear(ObfuscationRule::MixedCaseWords, 0.9);
+        }
+
+        // Example: "this is a download"
+        if spacesdots >= 3 {
+            return clear(ObfuscationRule::SeparatedWords, 0.8);
+        }
+
+        // Example: "Beast 2020"
+        if (upperchars + lowerchars >= 4) && decimals >= 4 && spacesdots >= 1 {
+            return clear(ObfuscationRule::WordsAndNumber, 0.8);
+        }
+
+        // Example: "Catullus", starts with a capital, and most letters are lower case
+        if filestem.chars().next().is_some_and(char::is_uppercase)
+            && lowerchars > 2
+            && (upperchars as f64) / (lowerchars as f64) <= 0.25
+        {
+            return clear(ObfuscationRule::Capitalized, 0.7);
+        }
+
+        // Example: "ヴァイオレット・エヴァーガーデン", whose letters have no case
+        if self.non_ascii_titles && non_ascii_letters >= 2 {
+            return clear(ObfuscationRule::NonAsciiLetters, 0.7);
+        }
+
+        // Example: "DUNE"
+        if let Some(min_len) = self.all_caps_min_len
+            && lowerchars == 0
+            && upperchars as usize >= min_len.max(1)
+        {
+            return clear(ObfuscationRule::AllCaps, 0.6);
+        }
+
+        // Finally: default to obfuscated
+        ObfuscationAnalysis::new(true, ObfuscationRule::Fallback, 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("b082fa0beaa644d3aa01045d5b8d0b36", true, ObfuscationRule::Hex32)]
+    #[case(
+        "0675e29e9abfd2.f7d069dab0b853283cc1b069a25f82.6547",
+        true,
+        ObfuscationRule::HexAndDots
+    )]
+    #[case(
+        "[BlaBla] something [More] something b2.bef89a622e4a23f07b0d3757ad5e8a.a0 [Brrr]",
+        true,
+        ObfuscationRule::BracketedHex
+    )]
+    #[case("abc.xyz.a4c567edbcbf27.BLA", true, ObfuscationRule::AbcXyz)]
+    #[case("Great Distro", false, ObfuscationRule::MixedCaseWords)]
+    #[case("this is a download", false, ObfuscationRule::SeparatedWords)]
+    #[case("Beast 2020", false, ObfuscationRule::WordsAndNumber)]
+    #[case("Catullus", false, ObfuscationRule::Capitalized)]
+    #[case("ヴァイオレット・エヴァーガーデン", true, ObfuscationRule::Fallback)]
+    #[case("DUNE", true, ObfuscationRule::Fallback)]
+    #[case("MUGNjK3zi65TtN", true, ObfuscationRule::Fallback)]
+    fn test_default_rules(#[case] filestem: &str, #[case] obfuscated: bool, #[case] rule: ObfuscationRule) {
+        let analysis = ObfuscationDetector::new().analyze(filestem);
+        assert_eq!(analysis.is_obfuscated(), obfuscated);
+        assert_eq!(analysis.rule(), &rule);
+        assert_eq!(subject::is_obfuscated(filestem), obfuscated);
+    }
+
+    #[rstest]
+    #[case("ヴァイオレット・エヴァーガーデン", false, ObfuscationRule::NonAsciiLetters)]
+    #[case("DUNE", false, ObfuscationRule::AllCaps)]
+    #[case("ABC", true, ObfuscationRule::Fallback)]
+    #[case("[a] [b] 0123456789abcdef", true, ObfuscationRule::BracketedHex)]
+    #[case("0123456789.abcdef", true, ObfuscationRule::HexAndDots)]
+    #[case("b082fa0beaa644d3aa01045d5b8d0b36", false, ObfuscationRule::Custom("allow".to_owned()))]
+    fn test_configured_rules(#[case] filestem: &str, #[case] obfuscated: bool, #[case] rule: ObfuscationRule) {
+        let detector = ObfuscationDetector::new()
+            .hex_run_len(16)
+            .hex_and_dots_min_len(16)
+            .non_ascii_titles(true)
+            .all_caps_titles(4)
+            .rule("allow", 2.0, |stem| stem.starts_with("b082").then_some(false));
+        let analysis = detector.analyze(filestem);
+        assert_eq!(analysis.is_obfuscated(), obfuscated);
+        assert_eq!(analysis.rule(), &rule);
+        assert!((0.0..=1.0).contains(&analysis.confidence()));
+    }
+
+    #[rstest]
+    #[case(0.75, 0.75)]
+    #[case(2.0, 1.0)]
+    #[case(-1.0, 0.0)]
+    #[case(f32::INFINITY, 1.0)]
+    #[case(f32::NEG_INFINITY, 0.0)]
+    #[case(f32::NAN, 0.0)]
+    fn test_custom_rule_confidence(#[case] confidence: f32, #[case] expected: f32) {
+        let detector = ObfuscationDetector::new().rule("custom", confidence, |_| Some(true));
+        assert_eq!(detector.analyze("abc").confidence(), expected);
+    }
+}
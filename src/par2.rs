@@ -0,0 +1,89 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::subject;
+
+/// The role of a `.par2` file in a PAR2 recovery set, as indicated by its name.
+///
+/// A recovery set consists of a small index file, e.g., `movie.par2`, which only describes
+/// the protected files, and any number of recovery volumes, e.g., `movie.vol03+04.par2`,
+/// which additionally hold the recovery blocks used for repair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Par2Volume {
+    /// An index file without recovery blocks, e.g., `movie.par2`.
+    Index,
+    /// A recovery volume, e.g., `movie.vol03+04.par2`.
+    Recovery {
+        /// Number of the first recovery block in the volume, e.g., `3`.
+        first_block: u32,
+        /// Number of recovery blocks in the volume, e.g., `4`.
+        block_count: u32,
+    },
+}
+
+impl Par2Volume {
+    /// Parses the volume from the name of a `.par2` file (case-insensitive).
+    ///
+    /// Returns `None` if the name does not end with `.par2`. Any `.par2` file that is not named
+    /// like a recovery volume (`.volXX+YY.par2`, or `.volXX-YY.par2` as some tools spell it)
+    /// is treated as an index file.
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        static RECOVERY_VOLUME: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(?i)\.vol(\d+)[+-](\d+)\.par2$").unwrap());
+
+        if !subject::is_par2(name) {
+            return None;
+        }
+
+        let volume = RECOVERY_VOLUME
+            .captures(name)
+            .and_then(|captures| {
+                Some(Self::Recovery {
+                    first_block: captures[1].parse().ok()?,
+                    block_count: captures[2].parse().ok()?,
+                })
+            })
+            .unwrap_or(Self::Index);
+
+        Some(volume)
+    }
+
+    /// Number of recovery blocks in the volume, which is `0` for an index file.
+    #[must_use]
+    pub fn block_count(&self) -> u32 {
+        match self {
+            Self::Index => 0,
+            Self::Recovery { block_count, .. } => *block_count,
+        }
+    }
+
+    /// Return [`true`] if the volume is an index file, [`false`] otherwise.
+    #[must_use]
+    pub fn is_index(&self) -> bool {
+        matches!(self, Self::Index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("movie.par2", Some(Par2Volume::Index))]
+    #[case("movie.mkv.PAR2", Some(Par2Volume::Index))]
+    #[case("movie.vol00+01.par2", Some(Par2Volume::Recovery { first_block: 0, block_count: 1 }))]
+    #[case("movie.vol03+04.par2", Some(Par2Volume::Recovery { first_block: 3, block_count: 4 }))]
+    #[case("movie.VOL127+128.Par2", Some(Par2Volume::Recovery { first_block: 127, block_count: 128 }))]
+    #[case("movie.vol1+99999999999.par2", Some(Par2Volume::Index))]
+    #[case("movie.vol03-04.par2", Some(Par2Volume::Recovery { first_block: 3, block_count: 4 }))]
+    #[case("movie.vol03_04.par2", Some(Par2Volume::Index))]
+    #[case("movie.vol03+04.par", None)]
+    #[case("movie.rar", None)]
+    fn test_parse(#[case] name: &str, #[case] expected: Option<Par2Volume>) {
+        assert_eq!(Par2Volume::parse(name), expected);
+    }
+}
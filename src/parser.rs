@@ -1,8 +1,9 @@
-use chrono::DateTime;
-use roxmltree::Document;
+use chrono::{DateTime, Utc};
 
-use crate::errors::{FileAttributeKind, ParseNzbError};
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
+use crate::errors::{FileAttributeKind, FileLocation, ParseNzbError, TextPosition};
 use crate::subject;
+use crate::xml::Document;
 use crate::{File, Segment};
 
 /// Parse all `<file>` elements from an NZB Document.
@@ -12,60 +13,64 @@ use crate::{File, Segment};
 /// - at least one `<group>` inside `<groups>`
 /// - at least one `<segment>` inside `<segments>`
 ///
-/// Segments missing required attributes (`bytes`, `number`) or message IDs
-/// are skipped rather than causing a hard error.
-pub(crate) fn parse_files(nzb: &Document) -> Result<Vec<File>, ParseNzbError> {
+/// Segments missing required attributes (`bytes`, `number`) or message IDs,
+/// and empty groups, are skipped rather than causing a hard error.
+/// Each skipped element is recorded as a [`Diagnostic`], unless `strict` is set,
+/// in which case the first one is returned as [`ParseNzbError::Diagnostic`].
+pub(crate) fn parse_files(nzb: &Document, strict: bool) -> Result<(Vec<File>, Vec<Diagnostic>), ParseNzbError> {
     let mut files = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    let root = nzb.roxmltree().root_element();
+
+    for (file_index, node) in root.children().filter(|n| n.has_tag_name("file")).enumerate() {
+        let position = nzb.position(node);
+        let missing = |kind| ParseNzbError::FileAttribute {
+            kind,
+            location: FileLocation::new(Some(file_index), node.attribute("subject"), Some(position)),
+        };
 
-    for node in nzb.root_element().children().filter(|n| n.has_tag_name("file")) {
         let poster = node
             .attribute("poster")
-            .ok_or(ParseNzbError::FileAttribute(FileAttributeKind::Poster))?;
+            .ok_or_else(|| missing(FileAttributeKind::Poster))?;
 
         let posted_at = node
-            .attribute("date") // Unix timestamp in seconds
-            .and_then(|d| d.parse::<i64>().ok())
-            .and_then(|d| DateTime::from_timestamp(d, 0))
-            .ok_or(ParseNzbError::FileAttribute(FileAttributeKind::Date))?;
+            .attribute("date")
+            .and_then(parse_date)
+            .ok_or_else(|| missing(FileAttributeKind::Date))?;
 
         let subject = node
             .attribute("subject")
-            .ok_or(ParseNzbError::FileAttribute(FileAttributeKind::Subject))?;
+            .ok_or_else(|| missing(FileAttributeKind::Subject))?;
 
         let mut groups = Vec::new();
         let mut segments = Vec::new();
 
+        let mut report = |element_index, kind| {
+            let diagnostic = Diagnostic::new(file_index, subject, element_index, kind);
+            if strict {
+                return Err(ParseNzbError::Diagnostic(diagnostic));
+            }
+            diagnostics.push(diagnostic);
+            Ok(())
+        };
+
         for child in node.children() {
             match child.tag_name().name() {
                 "groups" => {
-                    for group in child.children().filter(|n| n.has_tag_name("group")) {
-                        if let Some(text) = group.text()
-                            && !text.is_empty()
-                        {
-                            groups.push(text.to_owned());
+                    for (index, group) in child.children().filter(|n| n.has_tag_name("group")).enumerate() {
+                        match group.text() {
+                            Some(text) if !text.is_empty() => groups.push(text.to_owned()),
+                            _ => report(index, DiagnosticKind::EmptyGroup)?,
                         }
                     }
                 }
 
                 "segments" => {
-                    for segment in child.children().filter(|n| n.has_tag_name("segment")) {
-                        // Message-ID text is required and must be non-empty.
-                        if let Some(message_id) = segment.text()
-                            && !message_id.is_empty()
-                        {
-                            // Article size is typically ~700KB and safely fits in u32.
-                            let Some(size) = segment.attribute("bytes").and_then(|bytes| bytes.parse::<u32>().ok())
-                            else {
-                                continue;
-                            };
-                            let Some(number) = segment
-                                .attribute("number")
-                                .and_then(|number| number.parse::<u32>().ok())
-                            else {
-                                continue;
-                            };
-
-                            segments.push(Segment::new(size, number, message_id));
+                    for (index, segment) in child.children().filter(|n| n.has_tag_name("segment")).enumerate() {
+                        match parse_segment(segment.attribute("bytes"), segment.attribute("number"), segment.text()) {
+                            Ok(segment) => segments.push(segment),
+                            Err(kind) => report(index, kind)?,
                         }
                     }
                 }
@@ -73,23 +78,74 @@ pub(crate) fn parse_files(nzb: &Document) -> Result<Vec<File>, ParseNzbError> {
             }
         }
 
-        // A file must belong to at least one group.
-        if groups.is_empty() {
-            return Err(ParseNzbError::GroupsElement);
-        }
+        let file = File::new(poster, posted_at, subject, groups, segments);
+        files.push(validate_file(file, Some(file_index), Some(position))?);
+    }
 
-        // A file must contain at least one valid segment.
-        if segments.is_empty() {
-            return Err(ParseNzbError::SegmentsElement);
-        }
+    Ok((validate_files(files)?, diagnostics))
+}
 
-        // Sort for consistency
-        groups.sort_unstable();
-        segments.sort_unstable_by_key(|segment| segment.number());
+/// Parse the `date` attribute of a `<file>`, which is a Unix timestamp in seconds.
+pub(crate) fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    date.parse::<i64>().ok().and_then(|d| DateTime::from_timestamp(d, 0))
+}
 
-        files.push(File::new(poster, posted_at, subject, groups, segments));
+/// Parse a `<segment>` from its `bytes` and `number` attributes and its `Message-ID` text.
+///
+/// Returns the [`DiagnosticKind`] of the first one that is missing or invalid.
+pub(crate) fn parse_segment(
+    bytes: Option<&str>,
+    number: Option<&str>,
+    message_id: Option<&str>,
+) -> Result<Segment, DiagnosticKind> {
+    // Message-ID text is required and must be non-empty.
+    let message_id = message_id
+        .filter(|message_id| !message_id.is_empty())
+        .ok_or(DiagnosticKind::SegmentMessageId)?;
+    // Article size is typically ~700KB and safely fits in u32.
+    let size = bytes
+        .and_then(|bytes| bytes.parse::<u32>().ok())
+        .ok_or(DiagnosticKind::SegmentBytes)?;
+    let number = number
+        .and_then(|number| number.parse::<u32>().ok())
+        .ok_or(DiagnosticKind::SegmentNumber)?;
+    Ok(Segment::new(size, number, message_id))
+}
+
+/// Check that a [`File`] satisfies the invariants required of every `<file>`
+/// in an NZB, sorting its groups and segments for consistency.
+///
+/// A file must contain at least one group and at least one segment.
+/// The `index` and `position` of the file, if known, are attached to the error.
+pub(crate) fn validate_file(
+    mut file: File,
+    index: Option<usize>,
+    position: Option<TextPosition>,
+) -> Result<File, ParseNzbError> {
+    let location = || FileLocation::new(index, Some(file.subject()), position);
+
+    // A file must belong to at least one group.
+    if file.groups().is_empty() {
+        return Err(ParseNzbError::GroupsElement { location: location() });
+    }
+
+    // A file must contain at least one valid segment.
+    if file.segments().is_empty() {
+        return Err(ParseNzbError::SegmentsElement { location: location() });
     }
 
+    // Sort for consistency
+    file.sort();
+
+    Ok(file)
+}
+
+/// Check that a list of already validated files makes up a valid NZB,
+/// and sort them into their natural order.
+///
+/// The list must contain at least one file, and at least one of them must
+/// not be a `.par2` file.
+pub(crate) fn validate_files(mut files: Vec<File>) -> Result<Vec<File>, ParseNzbError> {
     // The NZB must contain at least one <file>.
     if files.is_empty() {
         return Err(ParseNzbError::FileElement);
@@ -0,0 +1,99 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// The position of a file in a multi-volume RAR archive, as indicated by its name.
+///
+/// Both naming schemes are recognized (case-insensitive):
+/// - New-style: `movie.part01.rar`, `movie.part02.rar`, ... (any number of digits)
+/// - Old-style: `movie.rar`, `movie.r00` ... `movie.r99`, `movie.s00` ... `movie.v99`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RarVolume<'a> {
+    base: &'a str,
+    number: u32,
+}
+
+impl<'a> RarVolume<'a> {
+    /// Parses the volume from the name of a RAR file.
+    ///
+    /// Returns `None` if the name has no RAR-related extension.
+    pub(crate) fn parse(name: &'a str) -> Option<Self> {
+        static NEW_STYLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^(.+)\.part(\d+)\.rar$").unwrap());
+
+        if let Some(captures) = NEW_STYLE.captures(name)
+            && let Some(number) = captures[2].parse::<u32>().ok().and_then(|n| n.checked_sub(1))
+        {
+            let base = captures.get(1)?.as_str();
+            return Some(Self { base, number });
+        }
+
+        // Old-style names, which are all 4 ASCII characters long, see `subject::is_rar`.
+        let (base, ext) = name.rsplit_once('.')?;
+        if base.is_empty() {
+            return None;
+        }
+        let number = match ext.as_bytes() {
+            [b'r' | b'R', b'a' | b'A', b'r' | b'R'] => 0,
+            [
+                letter @ (b'r'..=b'v' | b'R'..=b'V'),
+                tens @ b'0'..=b'9',
+                ones @ b'0'..=b'9',
+            ] => {
+                let series = u32::from(letter.to_ascii_lowercase() - b'r');
+                series * 100 + u32::from(tens - b'0') * 10 + u32::from(ones - b'0') + 1
+            }
+            _ => return None,
+        };
+
+        Some(Self { base, number })
+    }
+
+    /// Name shared by all the volumes of the archive, e.g., `movie` for `movie.part01.rar` or `movie.r00`.
+    #[must_use]
+    pub fn base(&self) -> &'a str {
+        self.base
+    }
+
+    /// Zero-based number of the volume in extraction order,
+    /// e.g., `0` for `movie.part01.rar` or `movie.rar`, and `1` for `movie.part02.rar` or `movie.r00`.
+    #[must_use]
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Return [`true`] if this is the first volume of the archive, [`false`] otherwise.
+    #[must_use]
+    pub fn is_first(&self) -> bool {
+        self.number == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("movie.part01.rar", Some(("movie", 0)))]
+    #[case("movie.part1.rar", Some(("movie", 0)))]
+    #[case("movie.PART002.RAR", Some(("movie", 1)))]
+    #[case("movie.2024.part10.rar", Some(("movie.2024", 9)))]
+    #[case("movie.part0.rar", Some(("movie.part0", 0)))]
+    #[case("movie.rar", Some(("movie", 0)))]
+    #[case("movie.r00", Some(("movie", 1)))]
+    #[case("movie.R99", Some(("movie", 100)))]
+    #[case("movie.s00", Some(("movie", 101)))]
+    #[case("movie.v99", Some(("movie", 500)))]
+    #[case("movie.w00", None)]
+    #[case("movie.r0", None)]
+    #[case("movie.mkv", None)]
+    #[case(".rar", None)]
+    #[case("rar", None)]
+    fn test_parse(#[case] name: &str, #[case] expected: Option<(&str, u32)>) {
+        assert_eq!(
+            RarVolume::parse(name).map(|volume| (volume.base(), volume.number())),
+            expected
+        );
+    }
+}
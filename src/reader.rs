@@ -0,0 +1,445 @@
+use std::io::{self, BufRead, Read};
+
+use quick_xml::XmlVersion;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+
+use crate::errors::{FileAttributeKind, FileLocation, ParseNzbError, TextPosition};
+use crate::file::File;
+use crate::meta::Meta;
+use crate::parser::{parse_date, parse_segment, validate_file};
+
+/// The start tag of a top-level `<file>` element, whether it was self-closing, and where it starts.
+type FileStart = (BytesStart<'static>, bool, TextPosition);
+
+/// A [`BufRead`] adapter that keeps track of the position of the consumed input,
+/// so that the byte offsets reported by `quick_xml` can be turned into a [`TextPosition`].
+///
+/// Only the input consumed since the last [`Tracker::mark`] is kept,
+/// which is never more than the event that is currently being read.
+#[derive(Debug)]
+struct Tracker<R> {
+    inner: R,
+    /// Byte offset of the mark in the input.
+    offset: u64,
+    /// Position of the mark in the input.
+    position: TextPosition,
+    /// Input consumed since the mark.
+    recent: Vec<u8>,
+}
+
+impl<R> Tracker<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            offset: 0,
+            position: TextPosition { line: 1, column: 1 },
+            recent: Vec::new(),
+        }
+    }
+
+    /// Moves the mark to the end of the input consumed so far.
+    fn mark(&mut self) {
+        self.position = advance(self.position, &self.recent);
+        self.offset += self.recent.len() as u64;
+        self.recent.clear();
+    }
+
+    /// Position of the byte `offset` in the input, which should not precede the mark.
+    fn position_at(&self, offset: u64) -> TextPosition {
+        let len = usize::try_from(offset.saturating_sub(self.offset)).unwrap_or(usize::MAX);
+        advance(self.position, &self.recent[..len.min(self.recent.len())])
+    }
+}
+
+impl<R: BufRead> Read for Tracker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for Tracker<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // The consumed bytes are still buffered, so this does not read anything.
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.recent.extend_from_slice(&buf[..amount.min(buf.len())]);
+        }
+        self.inner.consume(amount);
+    }
+}
+
+/// Advances `position` past `bytes`, counting columns in characters.
+fn advance(mut position: TextPosition, bytes: &[u8]) -> TextPosition {
+    for &byte in bytes {
+        if byte == b'\n' {
+            position.line = position.line.saturating_add(1);
+            position.column = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Not a UTF-8 continuation byte, so the start of a character.
+            position.column = position.column.saturating_add(1);
+        }
+    }
+    position
+}
+
+/// Reads the next event into `buf`, reporting syntax errors at their position in the input.
+fn read_event<'b, R: BufRead>(
+    reader: &mut quick_xml::Reader<Tracker<R>>,
+    buf: &'b mut Vec<u8>,
+) -> Result<Event<'b>, ParseNzbError> {
+    buf.clear();
+    reader.get_mut().mark();
+    reader.read_event_into(buf).map_err(|error| {
+        let position = reader.get_ref().position_at(reader.error_position());
+        ParseNzbError::xml_syntax(error.to_string(), Some(position))
+    })
+}
+
+/// Places a syntax error without a position at `position`.
+fn at(position: TextPosition) -> impl Fn(ParseNzbError) -> ParseNzbError {
+    move |error| match error {
+        ParseNzbError::XmlSyntax {
+            message,
+            position: None,
+        } => ParseNzbError::xml_syntax(message, Some(position)),
+        error => error,
+    }
+}
+
+/// The container a `<file>` child element is currently being read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Groups,
+    Segments,
+    Other,
+}
+
+/// Streaming NZB reader that yields [`File`]s one at a time from any [`BufRead`].
+///
+/// Unlike [`Nzb::parse`](crate::Nzb::parse), which builds the whole document in
+/// memory before returning, [`NzbReader`] only ever holds a single `<file>` element,
+/// which keeps memory usage flat even for very large NZBs.
+///
+/// Every yielded [`File`] is validated the same way the parser does. Once the end of
+/// the document is reached, the document-wide checks are applied and reported as a
+/// final error item if they fail, i.e., [`ParseNzbError::FileElement`] if the document
+/// contained no files, and [`ParseNzbError::OnlyPar2Files`] if all of them were `.par2`
+/// files. The reader stops after the first error.
+///
+/// Files are yielded in document order. Unlike [`Nzb::files`](crate::Nzb::files),
+/// they are not sorted, since that would require reading the entire document first.
+///
+/// The input must be UTF-8. Unlike [`Nzb::from_bytes`](crate::Nzb::from_bytes),
+/// the reader does not detect or transcode other character encodings.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fs;
+/// use std::io::BufReader;
+///
+/// use nzb_rs::NzbReader;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let file = fs::File::open("tests/nzbs/big_buck_bunny.nzb")?;
+///     let mut reader = NzbReader::new(BufReader::new(file))?;
+///     assert_eq!(reader.meta().title(), None);
+///
+///     let mut size = 0;
+///     for file in &mut reader {
+///         size += file?.size();
+///     }
+///     assert_eq!(size, 22_704_889);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NzbReader<R: BufRead> {
+    reader: quick_xml::Reader<Tracker<R>>,
+    buf: Vec<u8>,
+    meta: Meta,
+    /// Number of elements that are currently open.
+    depth: usize,
+    /// A `<file>` start tag that was read ahead while collecting the metadata.
+    pending: Option<FileStart>,
+    files: usize,
+    has_non_par2: bool,
+    finished: bool,
+}
+
+impl<R: BufRead> NzbReader<R> {
+    /// Creates a new [`NzbReader`].
+    ///
+    /// This reads the document up to the first `<file>` element so that
+    /// the metadata in `<head>` is available through [`NzbReader::meta`]
+    /// before any file is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseNzbError::XmlSyntax`] if the XML leading up to the first file is malformed.
+    pub fn new(reader: R) -> Result<Self, ParseNzbError> {
+        let mut reader = Self {
+            reader: quick_xml::Reader::from_reader(Tracker::new(reader)),
+            buf: Vec::new(),
+            meta: Meta::default(),
+            depth: 0,
+            pending: None,
+            files: 0,
+            has_non_par2: false,
+            finished: false,
+        };
+        reader.pending = reader.next_file_start()?;
+        Ok(reader)
+    }
+
+    /// Optional creator-definable metadata for the contents of the NZB.
+    ///
+    /// This contains every `<meta>` element read so far, which, for spec compliant
+    /// documents, is all of them, since `<head>` must precede the files.
+    #[must_use]
+    pub fn meta(&self) -> &Meta {
+        &self.meta
+    }
+
+    /// Position of the start of the event that was read last.
+    fn event_position(&self) -> TextPosition {
+        self.reader.get_ref().position
+    }
+
+    /// Advances the reader to the start of the next top-level `<file>` element,
+    /// collecting any `<meta>` elements along the way.
+    ///
+    /// Returns `None` once the end of the document is reached.
+    fn next_file_start(&mut self) -> Result<Option<FileStart>, ParseNzbError> {
+        loop {
+            match read_event(&mut self.reader, &mut self.buf)? {
+                Event::Start(start) => {
+                    let start = start.into_owned();
+                    let position = self.event_position();
+                    self.depth += 1;
+                    match start.local_name().as_ref() {
+                        "file" if self.depth == 2 => return Ok(Some((start, false, position))),
+                        "meta" => {
+                            let typ = attribute(&start, "type").map_err(at(position))?;
+                            let text = self.read_text()?;
+                            if let Some(typ) = typ {
+                                self.meta.append(typ, text);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Empty(start) if self.depth == 1 && start.local_name().as_ref() == "file" => {
+                    return Ok(Some((start.into_owned(), true, self.event_position())));
+                }
+                Event::End(_) => self.depth = self.depth.saturating_sub(1),
+                Event::Eof if self.depth > 0 => {
+                    return Err(ParseNzbError::xml_syntax(
+                        "the root node was opened but never closed",
+                        None,
+                    ));
+                }
+                Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the text content of the element whose start tag was just read,
+    /// consuming everything up to and including its end tag.
+    ///
+    /// Text inside nested elements is ignored.
+    fn read_text(&mut self) -> Result<String, ParseNzbError> {
+        let mut text = String::new();
+        let mut depth = 0usize;
+
+        loop {
+            match read_event(&mut self.reader, &mut self.buf)? {
+                Event::Text(t) if depth == 0 => text.push_str(&t.xml_content(XmlVersion::Implicit1_0)),
+                Event::CData(t) if depth == 0 => text.push_str(&t.xml_content(XmlVersion::Implicit1_0)),
+                Event::GeneralRef(r) if depth == 0 => {
+                    push_reference(&mut text, &r).map_err(at(self.reader.get_ref().position))?;
+                }
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => break,
+                Event::End(_) => depth -= 1,
+                Event::Eof => return Err(unexpected_eof()),
+                _ => {}
+            }
+        }
+
+        self.depth -= 1;
+        Ok(text)
+    }
+
+    /// Reads the remainder of a `<file>` element whose start tag was just read.
+    fn read_file(&mut self, start: &BytesStart, empty: bool, position: TextPosition) -> Result<File, ParseNzbError> {
+        let index = self.files;
+        let subject = attribute(start, "subject").map_err(at(position))?;
+        let missing = |kind| ParseNzbError::FileAttribute {
+            kind,
+            location: FileLocation::new(Some(index), subject.as_deref(), Some(position)),
+        };
+
+        let poster = attribute(start, "poster")
+            .map_err(at(position))?
+            .ok_or_else(|| missing(FileAttributeKind::Poster))?;
+
+        let posted_at = attribute(start, "date")
+            .map_err(at(position))?
+            .as_deref()
+            .and_then(parse_date)
+            .ok_or_else(|| missing(FileAttributeKind::Date))?;
+
+        let subject = subject.clone().ok_or_else(|| missing(FileAttributeKind::Subject))?;
+
+        let mut groups = Vec::new();
+        let mut segments = Vec::new();
+        let mut section = Section::Other;
+        let file_depth = self.depth;
+
+        if !empty {
+            loop {
+                match read_event(&mut self.reader, &mut self.buf)? {
+                    Event::Start(start) => {
+                        let start = start.into_owned();
+                        let element_position = self.event_position();
+                        self.depth += 1;
+
+                        match (self.depth - file_depth, section, start.local_name().as_ref()) {
+                            (1, _, "groups") => section = Section::Groups,
+                            (1, _, "segments") => section = Section::Segments,
+                            (1, _, _) => section = Section::Other,
+                            (2, Section::Groups, "group") => {
+                                let text = self.read_text()?;
+                                if !text.is_empty() {
+                                    groups.push(text);
+                                }
+                            }
+                            (2, Section::Segments, "segment") => {
+                                let bytes = attribute(&start, "bytes").map_err(at(element_position))?;
+                                let number = attribute(&start, "number").map_err(at(element_position))?;
+                                let message_id = self.read_text()?;
+                                if let Ok(segment) =
+                                    parse_segment(bytes.as_deref(), number.as_deref(), Some(&message_id))
+                                {
+                                    segments.push(segment);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    Event::End(_) if self.depth == file_depth => {
+                        self.depth -= 1;
+                        break;
+                    }
+                    Event::End(_) => self.depth -= 1,
+                    Event::Eof => return Err(unexpected_eof()),
+                    _ => {}
+                }
+            }
+        }
+
+        validate_file(
+            File::new(poster, posted_at, subject, groups, segments),
+            Some(index),
+            Some(position),
+        )
+    }
+
+    /// Reads the next file, or applies the document-wide checks once there are no more files.
+    fn next_file(&mut self) -> Result<Option<File>, ParseNzbError> {
+        let start = match self.pending.take() {
+            Some(start) => Some(start),
+            None => self.next_file_start()?,
+        };
+
+        let Some((start, empty, position)) = start else {
+            // The NZB must contain at least one <file>.
+            if self.files == 0 {
+                return Err(ParseNzbError::FileElement);
+            }
+
+            // Reject NZBs that contain only PAR2 repair files.
+            if !self.has_non_par2 {
+                return Err(ParseNzbError::OnlyPar2Files);
+            }
+
+            return Ok(None);
+        };
+
+        let file = self.read_file(&start, empty, position)?;
+
+        self.files += 1;
+        self.has_non_par2 |= !file.is_par2();
+
+        Ok(Some(file))
+    }
+}
+
+impl<R: BufRead> Iterator for NzbReader<R> {
+    type Item = Result<File, ParseNzbError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.next_file() {
+            Ok(Some(file)) => Some(Ok(file)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(error) => {
+                self.finished = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl<R: BufRead> std::iter::FusedIterator for NzbReader<R> {}
+
+/// Returns the unescaped value of the attribute `name`, if present.
+fn attribute(start: &BytesStart, name: &str) -> Result<Option<String>, ParseNzbError> {
+    for attr in start.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        if attr.key.local_name().as_ref() == name {
+            return Ok(Some(attr.normalized_value(XmlVersion::Implicit1_0)?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolves a character or predefined entity reference and appends it to `text`.
+fn push_reference(text: &mut String, reference: &BytesRef) -> Result<(), ParseNzbError> {
+    if let Some(char) = reference.resolve_char_ref()? {
+        text.push(char);
+        return Ok(());
+    }
+
+    let name = reference.xml_content(XmlVersion::Implicit1_0);
+    match resolve_predefined_entity(&name) {
+        Some(resolved) => {
+            text.push_str(resolved);
+            Ok(())
+        }
+        None => Err(ParseNzbError::xml_syntax(
+            format!("unknown entity reference '&{name};'"),
+            None,
+        )),
+    }
+}
+
+fn unexpected_eof() -> ParseNzbError {
+    ParseNzbError::xml_syntax("unexpected end of document", None)
+}
@@ -0,0 +1,314 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+/// The source a release was ripped or captured from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReleaseSource {
+    /// `BluRay`, `Blu-ray`, `BDRip`, `BRRip`.
+    BluRay,
+    /// `WEB-DL`, `WEBDL`.
+    WebDl,
+    /// `WEBRip`.
+    WebRip,
+    /// `WEB`, without further detail.
+    Web,
+    /// `HDTV`.
+    Hdtv,
+    /// `DVD`, `DVDRip`.
+    Dvd,
+}
+
+/// The video codec of a release.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReleaseCodec {
+    /// `x264`, `H.264`, `H264`, `AVC`.
+    H264,
+    /// `x265`, `H.265`, `H265`, `HEVC`.
+    H265,
+    /// `AV1`.
+    Av1,
+    /// `XviD`.
+    Xvid,
+}
+
+/// Structured information parsed from a release name, such as
+/// `Big.Buck.Bunny.S01E01.1080p.WEB-DL.H.264-GROUP` or `[Group] Big Buck Bunny - 01`.
+///
+/// Parsing is heuristic and never fails, information that cannot be found is simply missing.
+///
+/// # Example
+///
+/// ```
+/// use nzb_rs::{ReleaseCodec, ReleaseInfo, ReleaseSource};
+///
+/// let release = ReleaseInfo::parse("Big.Buck.Bunny.2008.S01E01-E03.1080p.WEB-DL.H.264-GROUP");
+///
+/// assert_eq!(release.title(), "Big Buck Bunny");
+/// assert_eq!(release.year(), Some(2008));
+/// assert_eq!(release.seasons(), [1]);
+/// assert_eq!(release.episodes(), [1, 2, 3]);
+/// assert_eq!(release.resolution(), Some(1080));
+/// assert_eq!(release.source(), Some(ReleaseSource::WebDl));
+/// assert_eq!(release.codec(), Some(ReleaseCodec::H264));
+/// assert_eq!(release.group(), Some("GROUP"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReleaseInfo {
+    title: String,
+    year: Option<u16>,
+    seasons: Vec<u32>,
+    episodes: Vec<u32>,
+    resolution: Option<u32>,
+    source: Option<ReleaseSource>,
+    codec: Option<ReleaseCodec>,
+    group: Option<String>,
+}
+
+/// Season and episode markers, e.g., `S01E01`, `S01E01E02`, `S01E01-E03`, `S01-S03`, or `S01`.
+static SEASON_EPISODE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bS(\d{1,3})(?:-S?(\d{1,3}))?(?:E(\d{1,4})(?:-?E(\d{1,4})|-(\d{1,4}))?)?\b").unwrap()
+});
+/// Alternative episode marker, e.g., `1x01`.
+static CROSS_EPISODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b(\d{1,2})x(\d{2,3})\b").unwrap());
+/// Anime-style absolute episode number, e.g., `Title - 01` or `Title - 01v2`.
+static ABSOLUTE_EPISODE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\s-\s(\d{1,4})(?:v\d)?(?:\s|$)").unwrap());
+static YEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(19\d{2}|20\d{2})\b").unwrap());
+static RESOLUTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b(?:(\d{3,4})[pi]|(4k|uhd|2160p))\b").unwrap());
+static SOURCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(blu-?ray|bdrip|brrip|web-?dl|webrip|web|hdtv|dvdrip|dvd)\b").unwrap());
+static CODEC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(x264|h[. ]?264|avc|x265|h[. ]?265|hevc|av1|xvid)\b").unwrap());
+/// Scene-style release group at the end, e.g., `-GROUP`.
+static SCENE_GROUP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"-([A-Za-z0-9]+)$").unwrap());
+/// Hyphenated source and audio tags that look like a scene-style release group at the end, e.g., `WEB-DL`.
+static HYPHENATED_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:web-dl|web-rip|hd-dvd|blu-ray|dts-hd|dts-es|dts-x|dd-ex)$").unwrap());
+
+impl ReleaseInfo {
+    /// Parses a release name, such as an NZB title or a file name without its extension.
+    #[must_use]
+    pub fn parse(name: &str) -> Self {
+        let mut info = Self::default();
+        let mut name = name.trim();
+
+        // Anime-style release group at the start, e.g., `[Group] Title - 01`.
+        if let Some(rest) = name.strip_prefix('[')
+            && let Some((group, rest)) = rest.split_once(']')
+            && !group.trim().is_empty()
+        {
+            info.group = Some(group.trim().to_owned());
+            name = rest.trim_start();
+        }
+
+        // Scene-style names use dots or underscores instead of spaces.
+        let name = if name.contains(' ') {
+            name.to_owned()
+        } else {
+            name.replace(['.', '_'], " ")
+        };
+
+        // The title ends where the first marker starts.
+        let mut title_end = name.len();
+        let mut found = |range: Range<usize>| {
+            // A marker at the very start is part of the title, e.g., `2012 2009`.
+            if range.start > 0 {
+                title_end = title_end.min(range.start);
+            }
+        };
+
+        if let Some(captures) = SEASON_EPISODE.captures(&name) {
+            let season = number(&captures, 1).unwrap_or_default();
+            info.seasons = range(season, number(&captures, 2));
+            if let Some(episode) = number(&captures, 3) {
+                info.episodes = range(episode, number(&captures, 4).or_else(|| number(&captures, 5)));
+            }
+            found(captures.get_match().range());
+        } else if let Some(captures) = CROSS_EPISODE.captures(&name) {
+            info.seasons = number(&captures, 1).into_iter().collect();
+            info.episodes = number(&captures, 2).into_iter().collect();
+            found(captures.get_match().range());
+        } else if let Some(captures) = ABSOLUTE_EPISODE.captures(&name) {
+            info.episodes = number(&captures, 1).into_iter().collect();
+            found(captures.get_match().range());
+        }
+
+        if let Some(year) = YEAR.captures_iter(&name).find(|c| c.get_match().start() > 0) {
+            info.year = year[1].parse().ok();
+            found(year.get_match().range());
+        }
+
+        if let Some(captures) = RESOLUTION.captures(&name) {
+            info.resolution = number(&captures, 1).or(captures.get(2).map(|_| 2160));
+            found(captures.get_match().range());
+        }
+
+        if let Some(m) = SOURCE.find(&name) {
+            info.source = match m.as_str().to_ascii_lowercase().replace('-', "").as_str() {
+                "bluray" | "bdrip" | "brrip" => Some(ReleaseSource::BluRay),
+                "webdl" => Some(ReleaseSource::WebDl),
+                "webrip" => Some(ReleaseSource::WebRip),
+                "web" => Some(ReleaseSource::Web),
+                "hdtv" => Some(ReleaseSource::Hdtv),
+                _ => Some(ReleaseSource::Dvd),
+            };
+            found(m.range());
+        }
+
+        if let Some(m) = CODEC.find(&name) {
+            info.codec = match m.as_str().to_ascii_lowercase().replace(['.', ' '], "").as_str() {
+                "x264" | "h264" | "avc" => Some(ReleaseCodec::H264),
+                "x265" | "h265" | "hevc" => Some(ReleaseCodec::H265),
+                "av1" => Some(ReleaseCodec::Av1),
+                _ => Some(ReleaseCodec::Xvid),
+            };
+            found(m.range());
+        }
+
+        if info.group.is_none()
+            && title_end < name.len()
+            && let Some(captures) = SCENE_GROUP.captures(&name)
+            && !HYPHENATED_TAG.is_match(&name)
+        {
+            info.group = Some(captures[1].to_owned());
+        }
+
+        info.title = name[..title_end]
+            .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '(' | '['))
+            .trim()
+            .to_owned();
+
+        info
+    }
+
+    /// Title of the release, e.g., `Big Buck Bunny`.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Year of the release, e.g., `2008`.
+    #[must_use]
+    pub fn year(&self) -> Option<u16> {
+        self.year
+    }
+
+    /// Seasons in the release, e.g., `[1]` for `S01E01` and `[1, 2, 3]` for `S01-S03`.
+    #[must_use]
+    pub fn seasons(&self) -> &[u32] {
+        &self.seasons
+    }
+
+    /// Episodes in the release, e.g., `[1]` for `S01E01`, `[1, 2, 3]` for `S01E01-E03`,
+    /// and `[1]` for an anime-style `Title - 01`.
+    #[must_use]
+    pub fn episodes(&self) -> &[u32] {
+        &self.episodes
+    }
+
+    /// Vertical resolution of the release, e.g., `1080` for `1080p` and `2160` for `4K`.
+    #[must_use]
+    pub fn resolution(&self) -> Option<u32> {
+        self.resolution
+    }
+
+    /// Source of the release, e.g., [`ReleaseSource::BluRay`].
+    #[must_use]
+    pub fn source(&self) -> Option<ReleaseSource> {
+        self.source
+    }
+
+    /// Video codec of the release, e.g., [`ReleaseCodec::H264`].
+    #[must_use]
+    pub fn codec(&self) -> Option<ReleaseCodec> {
+        self.codec
+    }
+
+    /// Group that made the release, e.g., `GROUP` for `Title.1080p-GROUP` or `Group` for `[Group] Title - 01`.
+    #[must_use]
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Return [`true`] if the release is an episode, or a range of them, [`false`] otherwise.
+    #[must_use]
+    pub fn is_episode(&self) -> bool {
+        !self.episodes.is_empty()
+    }
+}
+
+/// Parses the capture group at `index` as a number, if it matched.
+fn number(captures: &Captures<'_>, index: usize) -> Option<u32> {
+    captures.get(index)?.as_str().parse().ok()
+}
+
+/// Expands `start` and an optional inclusive `end` into a list of numbers.
+fn range(start: u32, end: Option<u32>) -> Vec<u32> {
+    match end {
+        Some(end) if end > start => (start..=end).collect(),
+        _ => vec![start],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("Big Buck Bunny - S01E01", "Big Buck Bunny", None, &[1], &[1], None, None)]
+    #[case("Big.Buck.Bunny.S01E01E02.720p.HDTV.x264-GRP", "Big Buck Bunny", None, &[1], &[1, 2], Some(720), Some("GRP"))]
+    #[case("Big.Buck.Bunny.S02E05-07.2160p", "Big Buck Bunny", None, &[2], &[5, 6, 7], Some(2160), None)]
+    #[case("Big.Buck.Bunny.S01-S03.1080p.BluRay.x265-GRP", "Big Buck Bunny", None, &[1, 2, 3], &[], Some(1080), Some("GRP"))]
+    #[case("Big Buck Bunny 1x05", "Big Buck Bunny", None, &[1], &[5], None, None)]
+    #[case("Sintel.2010.1080p.BluRay.x264-GRP", "Sintel", Some(2010), &[], &[], Some(1080), Some("GRP"))]
+    #[case("2012.2009.4K.WEB-DL-GRP", "2012", Some(2009), &[], &[], Some(2160), Some("GRP"))]
+    #[case("Show.S01E01.1080p.WEB-DL", "Show", None, &[1], &[1], Some(1080), None)]
+    #[case("Sintel.2010.1080p.BluRay.DTS-HD", "Sintel", Some(2010), &[], &[], Some(1080), None)]
+    #[case("Sintel.2010.1080p.BluRay.DTS-HD-GRP", "Sintel", Some(2010), &[], &[], Some(1080), Some("GRP"))]
+    #[case("[Foobar] Violet Evergarden - 01 [1080p]", "Violet Evergarden", None, &[], &[1], Some(1080), Some("Foobar"))]
+    #[case("[Foobar] Violet Evergarden - 13v2", "Violet Evergarden", None, &[], &[13], None, Some("Foobar"))]
+    #[case("Some-Title", "Some-Title", None, &[], &[], None, None)]
+    #[case("", "", None, &[], &[], None, None)]
+    fn test_parse(
+        #[case] name: &str,
+        #[case] title: &str,
+        #[case] year: Option<u16>,
+        #[case] seasons: &[u32],
+        #[case] episodes: &[u32],
+        #[case] resolution: Option<u32>,
+        #[case] group: Option<&str>,
+    ) {
+        let release = ReleaseInfo::parse(name);
+        assert_eq!(release.title(), title);
+        assert_eq!(release.year(), year);
+        assert_eq!(release.seasons(), seasons);
+        assert_eq!(release.episodes(), episodes);
+        assert_eq!(release.resolution(), resolution);
+        assert_eq!(release.group(), group);
+    }
+
+    #[rstest]
+    #[case("Title.1080p.BluRay.x264", Some(ReleaseSource::BluRay), Some(ReleaseCodec::H264))]
+    #[case("Title.1080p.BDRip.AVC", Some(ReleaseSource::BluRay), Some(ReleaseCodec::H264))]
+    #[case("Title.1080p.WEBDL.H.265", Some(ReleaseSource::WebDl), Some(ReleaseCodec::H265))]
+    #[case("Title.1080p.WEBRip.HEVC", Some(ReleaseSource::WebRip), Some(ReleaseCodec::H265))]
+    #[case("Title 1080p WEB AV1", Some(ReleaseSource::Web), Some(ReleaseCodec::Av1))]
+    #[case("Title.DVDRip.XviD", Some(ReleaseSource::Dvd), Some(ReleaseCodec::Xvid))]
+    #[case("Title", None, None)]
+    fn test_source_and_codec(
+        #[case] name: &str,
+        #[case] source: Option<ReleaseSource>,
+        #[case] codec: Option<ReleaseCodec>,
+    ) {
+        let release = ReleaseInfo::parse(name);
+        assert_eq!(release.title(), "Title");
+        assert_eq!(release.source(), source);
+        assert_eq!(release.codec(), codec);
+    }
+}
@@ -1,8 +1,11 @@
+use std::ops::Range;
 use std::path::Path;
 use std::sync::LazyLock;
 
 use regex::Regex;
 
+use crate::obfuscation::DEFAULT_DETECTOR;
+
 /// Splits a string once on `delimiter`, trimming whitespace from both results.
 ///
 /// Returns `None` if the delimiter is not present.
@@ -49,17 +52,17 @@ fn is_counter(s: &str) -> bool {
     }
 }
 
-/// Returns `true` if the string contains at least 30 consecutive hexdigits
+/// Returns `true` if the string contains at least `len` consecutive hexdigits
 ///
 /// The run must be uninterrupted, any non-hexdigit resets the count.
-/// Equivalent to the regex `[a-fA-F0-9]{30}`.
-fn has_30_consecutive_hexdigits(s: &str) -> bool {
+/// Equivalent to the regex `[a-fA-F0-9]{len}`.
+pub(crate) fn has_consecutive_hexdigits(s: &str, len: usize) -> bool {
     let mut run = 0;
 
     for char in s.as_bytes() {
         if char.is_ascii_hexdigit() {
             run += 1;
-            if run >= 30 {
+            if run >= len {
                 return true;
             }
         } else {
@@ -74,7 +77,7 @@ fn has_30_consecutive_hexdigits(s: &str) -> bool {
 ///
 /// Each word matches `\[\w+\]` (non-empty alphanumeric or underscore content
 /// enclosed in square brackets).
-fn has_two_bracketed_words(s: &str) -> bool {
+pub(crate) fn has_two_bracketed_words(s: &str) -> bool {
     s.split('[')
         .skip(1)
         .filter_map(|part| part.split_once(']'))
@@ -96,6 +99,19 @@ fn has_two_bracketed_words(s: &str) -> bool {
 ///
 /// [`subject_name_extractor`]: https://github.com/sabnzbd/sabnzbd/blob/b5dda7c52d9055a3557e7f5fc6e76fe86c4c4365/sabnzbd/misc.py#L1642-L1655
 pub(crate) fn file_name(subject: &str) -> Option<&str> {
+    file_name_range(subject).map(|range| &subject[range])
+}
+
+/// Narrows `range` of `s` down to the part that is left after trimming the characters matching `pat`.
+fn trim_range(s: &str, range: Range<usize>, pat: impl Fn(char) -> bool + Copy) -> Range<usize> {
+    let part = &s[range.clone()];
+    let start = range.start + (part.len() - part.trim_start_matches(pat).len());
+    let end = range.end - (part.len() - part.trim_end_matches(pat).len());
+    start..end.max(start)
+}
+
+/// Byte range of the filename in the subject, see [`file_name`].
+fn file_name_range(subject: &str) -> Option<Range<usize>> {
     // The extraction logic is intentionally ordered from most specific to most
     // general to avoid false positives.
 
@@ -113,9 +129,9 @@ pub(crate) fn file_name(subject: &str) -> Option<&str> {
     {
         let start = start + 1;
         if start < end {
-            let s = subject[start..end].trim_matches(|c: char| c.is_whitespace() || c == '"');
-            if !s.is_empty() {
-                return Some(s);
+            let range = trim_range(subject, start..end, |c: char| c.is_whitespace() || c == '"');
+            if !range.is_empty() {
+                return Some(range);
             }
         }
     }
@@ -135,16 +151,16 @@ pub(crate) fn file_name(subject: &str) -> Option<&str> {
     // - filename: "[Foobar] Violet Evergarden - 01.mkv"
     // - chunk: "(1/2401)"
     // - trailing digits: "1720916370"
-    if let Some((part, rest)) = split_once_trimmed(subject, "-")
-        && is_counter(part)
-        && let Some((filename, rest)) = split_once_trimmed(rest, "yEnc")
-        && let Some((chunk, digits)) = split_once_trimmed(rest, " ")
+    if let Some(dash) = subject.find('-')
+        && is_counter(&subject[..dash])
+        && let Some(yenc) = subject[dash + 1..].find("yEnc").map(|i| dash + 1 + i)
+        && let Some((chunk, digits)) = split_once_trimmed(subject[yenc + "yEnc".len()..].trim(), " ")
         && is_counter(chunk)
         && is_number(digits)
     {
-        let trimmed = filename.trim();
-        if !trimmed.is_empty() {
-            return Some(trimmed);
+        let range = trim_range(subject, dash + 1..yenc, char::is_whitespace);
+        if !range.is_empty() {
+            return Some(range);
         }
     }
 
@@ -163,9 +179,9 @@ pub(crate) fn file_name(subject: &str) -> Option<&str> {
     });
 
     for matched in SABNZBD_SUBJECT_BASIC_FILENAME.find_iter(subject) {
-        let trimmed = matched.as_str().trim();
-        if !trimmed.is_empty() {
-            return Some(trimmed);
+        let range = trim_range(subject, matched.range(), char::is_whitespace);
+        if !range.is_empty() {
+            return Some(range);
         }
     }
 
@@ -261,89 +277,14 @@ pub(crate) fn is_par2(name: &str) -> bool {
 
 /// Return `true` if the file stem appears to be obfuscated, `false` otherwise.
 ///
-/// Based on SABnzbd’s [`is_probably_obfuscated`][0]. Unlike the original, this
-/// expects only the file stem (basename without extension) and does not handle
+/// This uses the default [`ObfuscationDetector`](crate::ObfuscationDetector),
+/// which is based on SABnzbd’s [`is_probably_obfuscated`][0]. Unlike the original,
+/// this expects only the file stem (basename without extension) and does not handle
 /// paths or extensions, so passing anything else will yield incorrect results.
 ///
 /// [0]: <https://github.com/sabnzbd/sabnzbd/blob/d21a1119932896c1f7fea1b804e99c70f05dbd19/sabnzbd/deobfuscate_filenames.py#L103>
 pub(crate) fn is_obfuscated(filestem: &str) -> bool {
-    // In a lot of cases, we do not care about anything other than ASCII characters.
-    // So, we can work on the byte level for minor performance gains.
-    let filestem_bytes = filestem.as_bytes();
-    let length = filestem_bytes.len();
-
-    // First, the patterns that are certainly obfuscated:
-
-    // 32-character hex strings, e.g.
-    // ...blabla.H.264/b082fa0beaa644d3aa01045d5b8d0b36.mkv is certainly obfuscated
-    if length == 32 && filestem_bytes.iter().all(u8::is_ascii_hexdigit) {
-        return true;
-    }
-
-    // 40+ chars consisting only of hex digits and dots, e.g.
-    // 0675e29e9abfd2.f7d069dab0b853283cc1b069a25f82.6547
-    if length >= 40 && filestem_bytes.iter().all(|b| b.is_ascii_hexdigit() || *b == b'.') {
-        return true;
-    }
-
-    // "[BlaBla] something [More] something 5937bc5e32146e.bef89a622e4a23f07b0d3757ad5e8a.a02b264e [Brrr]"
-    // So: square brackets plus 30+ hex digit
-    if has_30_consecutive_hexdigits(filestem) && has_two_bracketed_words(filestem) {
-        return true;
-    }
-
-    // /some/thing/abc.xyz.a4c567edbcbf27.BLA is certainly obfuscated
-    if filestem_bytes.starts_with(b"abc.xyz") {
-        return true;
-    }
-
-    // Then, patterns that are not obfuscated but typical, clear names:
-
-    // these are signals for the obfuscation versus non-obfuscation
-    let mut decimals: u32 = 0;
-    let mut upperchars: u32 = 0;
-    let mut lowerchars: u32 = 0;
-    let mut spacesdots: u32 = 0;
-
-    for char in filestem.chars() {
-        if char.is_ascii_digit() {
-            decimals += 1;
-        }
-        if char.is_uppercase() {
-            upperchars += 1;
-        }
-        if char.is_lowercase() {
-            lowerchars += 1;
-        }
-        if char == ' ' || char == '.' || char == '_' {
-            spacesdots += 1;
-        }
-    }
-
-    // Example: "Great Distro"
-    if upperchars >= 2 && lowerchars >= 2 && spacesdots >= 1 {
-        return false;
-    }
-
-    // Example: "this is a download"
-    if spacesdots >= 3 {
-        return false;
-    }
-
-    // Example: "Beast 2020"
-    if (upperchars + lowerchars >= 4) && decimals >= 4 && spacesdots >= 1 {
-        return false;
-    }
-
-    // Example: "Catullus", starts with a capital, and most letters are lower case
-    if filestem.chars().next().is_some_and(char::is_uppercase)
-        && lowerchars > 2
-        && (upperchars as f64) / (lowerchars as f64) <= 0.25
-    {
-        return false;
-    }
-    // Finally: default to obfuscated
-    true
+    DEFAULT_DETECTOR.analyze(filestem).is_obfuscated()
 }
 
 /// Extracts the file number from a subject, if present.
@@ -369,6 +310,255 @@ pub(crate) fn file_number(subject: &str) -> Option<(u32, u32)> {
         })
 }
 
+/// Returns `true` if any of the words in the name, separated by anything
+/// other than letters and digits, is one of `words` (case-insensitive).
+fn has_word(name: &str, words: &[&str]) -> bool {
+    name.split(|c: char| !c.is_alphanumeric())
+        .any(|word| words.iter().any(|w| word.eq_ignore_ascii_case(w)))
+}
+
+/// Returns `true` if the name marks a sample of the release, e.g., `movie-sample.mkv` or `Sample/movie.mkv`.
+pub(crate) fn is_sample(name: &str) -> bool {
+    has_word(name, &["sample"])
+}
+
+/// Returns `true` if the name marks a proof of the release, e.g., `movie-proof.jpg`.
+pub(crate) fn is_proof(name: &str) -> bool {
+    has_word(name, &["proof"])
+}
+
+/// Returns `true` if the name marks bonus material, e.g., `movie.featurette.mkv` or `Extras - Trailer.mkv`,
+/// or a file in a subtitles folder, e.g., `Subs/English.srt`.
+pub(crate) fn is_extra(name: &str) -> bool {
+    const BONUS: &[&str] = &[
+        "extra",
+        "extras",
+        "featurette",
+        "featurettes",
+        "trailer",
+        "trailers",
+        "bonus",
+    ];
+
+    has_word(name, BONUS)
+        || name
+            .rsplit_once(['/', '\\'])
+            .is_some_and(|(folder, _)| has_word(folder, &["subs", "subtitles"]))
+}
+
+/// Returns the name shared by all the files in a set of archive volumes and their `.par2` files.
+///
+/// The following suffixes are stripped (case-insensitive), in this order:
+///
+///  - `.par2`, `.volNN+MM.par2` and `.volNN-MM.par2`
+///  - `.partNN.rar`, `.rar` and `.rNN` (and `.sNN` ... `.vNN`, see [`is_rar`])
+///  - `.7z` and `.7z.NNN`, `.zip` and `.zNN`, and plain split `.NNN` files
+///
+/// So `movie.part01.rar`, `movie.r00`, `movie.vol00+01.par2` and `movie.7z.001` all become `movie`,
+/// while `movie.mkv` and `movie.mkv.vol00+01.par2` become `movie.mkv`.
+///
+/// The name is returned unchanged if stripping would leave nothing.
+pub(crate) fn set_name(name: &str) -> &str {
+    static PAR2_SUFFIX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(?:\.vol\d+[+-]\d+)?\.par2$").unwrap());
+    static ARCHIVE_SUFFIX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"(?i)\.(?:part\d+\.rar|rar|[r-v]\d{2}|7z(?:\.\d{3})?|zip|z\d{2}|\d{3})$").unwrap()
+    });
+
+    let base = PAR2_SUFFIX.find(name).map_or(name, |m| &name[..m.start()]);
+    let base = ARCHIVE_SUFFIX.find(base).map_or(base, |m| &base[..m.start()]);
+
+    if base.is_empty() { name } else { base }
+}
+
+/// A `current/total` counter in a subject, such as the `[1/5]` file counter
+/// or the `(1/24)` segment counter in `[1/5] - "file.mkv" yEnc (1/24) 16981056`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubjectCounter {
+    /// The number before the slash.
+    pub current: u32,
+    /// The number after the slash.
+    pub total: u32,
+}
+
+impl SubjectCounter {
+    /// Parses a counter in the form `[x/y]` or `(x/y)`, see [`is_counter`].
+    fn parse(s: &str) -> Option<Self> {
+        if !is_counter(s) {
+            return None;
+        }
+        let s = s.trim();
+        let (current, total) = s[1..s.len() - 1].split_once('/')?;
+        Some(Self {
+            current: current.trim().parse().ok()?,
+            total: total.trim().parse().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for SubjectCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.current, self.total)
+    }
+}
+
+/// The components of a Usenet subject.
+///
+/// Most posting tools generate subjects in the following layout,
+/// where every component other than the filename is optional:
+///
+/// ```text
+/// <prefix> [<file counter>] - "<filename>" yEnc (<segment counter>) <size>
+/// ```
+///
+/// The filename is extracted the same way as [`File::name`](crate::File::name),
+/// and the other components are looked for around it. If no filename can be
+/// extracted, only the counters, the yEnc marker and the size are looked for.
+///
+/// # Example
+///
+/// ```
+/// use nzb_rs::{Subject, SubjectCounter};
+///
+/// let subject = Subject::parse(r#"My Upload [1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#);
+///
+/// assert_eq!(subject.prefix(), Some("My Upload"));
+/// assert_eq!(subject.file_counter(), Some(SubjectCounter { current: 1, total: 5 }));
+/// assert_eq!(subject.file_name(), Some("Big Buck Bunny - S01E01.mkv"));
+/// assert!(subject.is_yenc());
+/// assert_eq!(subject.segment_counter(), Some(SubjectCounter { current: 1, total: 24 }));
+/// assert_eq!(subject.size(), Some(16981056));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Subject<'a> {
+    subject: &'a str,
+    prefix: Option<&'a str>,
+    file_counter: Option<SubjectCounter>,
+    file_name: Option<&'a str>,
+    is_yenc: bool,
+    segment_counter: Option<SubjectCounter>,
+    size: Option<u64>,
+}
+
+impl<'a> Subject<'a> {
+    /// Parses a Usenet subject into its components.
+    ///
+    /// This never fails, components that cannot be found are simply missing.
+    #[must_use]
+    pub fn parse(subject: &'a str) -> Self {
+        let (head, name, tail) = match file_name_range(subject) {
+            Some(range) => (
+                &subject[..range.start],
+                Some(&subject[range.clone()]),
+                &subject[range.end..],
+            ),
+            None => match subject.find("yEnc") {
+                Some(start) => (&subject[..start], None, &subject[start..]),
+                None => (subject, None, ""),
+            },
+        };
+
+        // Everything before the filename: `<prefix> [<file counter>] - "`
+        let head = head.trim_end_matches(|c: char| c.is_whitespace() || c == '"' || c == '-');
+        let (prefix, file_counter) = match head
+            .rfind(['[', '('])
+            .and_then(|start| Some((&head[..start], SubjectCounter::parse(&head[start..])?)))
+        {
+            Some((prefix, counter)) => (prefix, Some(counter)),
+            None => (head, None),
+        };
+        let prefix = prefix
+            .trim_end_matches(|c: char| c.is_whitespace() || c == '-')
+            .trim_start();
+
+        // Everything after the filename: `" yEnc (<segment counter>) <size>`
+        let tail = tail.trim_start_matches(|c: char| c.is_whitespace() || c == '"');
+        let (is_yenc, tail) = match tail.strip_prefix("yEnc") {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, tail),
+        };
+        let (segment_counter, tail) = match tail
+            .starts_with('(')
+            .then(|| tail.find(')'))
+            .flatten()
+            .and_then(|end| Some((SubjectCounter::parse(&tail[..=end])?, &tail[end + 1..])))
+        {
+            Some((counter, rest)) => (Some(counter), rest.trim_start()),
+            None => (None, tail),
+        };
+        let size = tail
+            .split_whitespace()
+            .next()
+            .filter(|size| is_number(size))
+            .and_then(|size| size.parse().ok());
+
+        Self {
+            subject,
+            prefix: (!prefix.is_empty() && name.is_some()).then_some(prefix),
+            file_counter,
+            file_name: name,
+            is_yenc,
+            segment_counter,
+            size,
+        }
+    }
+
+    /// The complete subject.
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.subject
+    }
+
+    /// Free-form text before the file counter and filename, e.g., `"Here's your file!"`.
+    /// May return [`None`] if there is no such text, or if the filename could not be extracted.
+    #[must_use]
+    pub fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+
+    /// Position of the file in the post, and the number of files in it, e.g., `[1/5]`.
+    /// May return [`None`] if the subject has no such counter.
+    #[must_use]
+    pub fn file_counter(&self) -> Option<SubjectCounter> {
+        self.file_counter
+    }
+
+    /// Complete name of the file with its extension.
+    /// May return [`None`] if it fails to extract the name.
+    #[must_use]
+    pub fn file_name(&self) -> Option<&'a str> {
+        self.file_name
+    }
+
+    /// Return [`true`] if the subject has a `yEnc` marker after the filename, [`false`] otherwise.
+    #[must_use]
+    pub fn is_yenc(&self) -> bool {
+        self.is_yenc
+    }
+
+    /// Segment counter of the first article, and the number of segments in the file, e.g., `(1/24)`.
+    /// May return [`None`] if the subject has no such counter.
+    #[must_use]
+    pub fn segment_counter(&self) -> Option<SubjectCounter> {
+        self.segment_counter
+    }
+
+    /// Size of the file in bytes, as declared by the poster.
+    /// May return [`None`] if the subject does not declare a size.
+    ///
+    /// Note that this is the size of the decoded file, whereas [`File::size`](crate::File::size)
+    /// is the size of the encoded articles, which is usually a few percent larger.
+    #[must_use]
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+impl std::fmt::Display for Subject<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.subject)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::seq::SliceRandom;
@@ -778,4 +968,166 @@ mod tests {
         assert_eq!(sorted_by_key, control);
         assert_eq!(sorted_by, sorted_by_key);
     }
+
+    #[rstest]
+    #[case("movie-sample.mkv", true, false, false)]
+    #[case("Sample/movie.mkv", true, false, false)]
+    #[case("movie.SAMPLE.part01.rar", true, false, false)]
+    #[case("movie.samples.mkv", false, false, false)]
+    #[case("movie-proof.jpg", false, true, false)]
+    #[case("Proof/cover.jpg", false, true, false)]
+    #[case("movie.featurette.mkv", false, false, true)]
+    #[case("Extras - Trailer.mkv", false, false, true)]
+    #[case("movie_bonus.mkv", false, false, true)]
+    #[case("Subs/English.srt", false, false, true)]
+    #[case("Movie\\Subtitles\\English.srt", false, false, true)]
+    #[case("movie.subs.srt", false, false, false)]
+    #[case("Sintel.2010.1080p.mkv", false, false, false)]
+    #[case("Extraordinary.mkv", false, false, false)]
+    fn test_is_sample_proof_extra(#[case] name: &str, #[case] sample: bool, #[case] proof: bool, #[case] extra: bool) {
+        assert_eq!(is_sample(name), sample);
+        assert_eq!(is_proof(name), proof);
+        assert_eq!(is_extra(name), extra);
+    }
+
+    #[rstest]
+    #[case("movie.part01.rar", "movie")]
+    #[case("movie.PART10.RAR", "movie")]
+    #[case("movie.rar", "movie")]
+    #[case("movie.r00", "movie")]
+    #[case("movie.s12", "movie")]
+    #[case("movie.par2", "movie")]
+    #[case("movie.vol00+01.par2", "movie")]
+    #[case("movie.vol127-255.PAR2", "movie")]
+    #[case("movie.7z", "movie")]
+    #[case("movie.7z.001", "movie")]
+    #[case("movie.7z.par2", "movie")]
+    #[case("movie.zip", "movie")]
+    #[case("movie.z01", "movie")]
+    #[case("movie.mkv.001", "movie.mkv")]
+    #[case("movie.mkv", "movie.mkv")]
+    #[case("movie.mkv.par2", "movie.mkv")]
+    #[case("movie.mkv.vol03+04.par2", "movie.mkv")]
+    #[case("movie.srt", "movie.srt")]
+    #[case(".par2", ".par2")]
+    #[case("movie", "movie")]
+    fn test_set_name(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(set_name(name), expected);
+    }
+
+    fn counter(current: u32, total: u32) -> Option<SubjectCounter> {
+        Some(SubjectCounter { current, total })
+    }
+
+    #[rstest]
+    #[case(
+        r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#,
+        None,
+        counter(1, 5),
+        Some("Big Buck Bunny - S01E01.mkv"),
+        true,
+        counter(1, 24),
+        Some(16981056)
+    )]
+    #[case(
+        r#"[011/116] - [Foobar] Violet Evergarden - 01.mkv yEnc (1/2401) 1720916370"#,
+        None,
+        counter(11, 116),
+        Some("[Foobar] Violet Evergarden - 01.mkv"),
+        true,
+        counter(1, 2401),
+        Some(1720916370)
+    )]
+    #[case(
+        "Here's your file!  abc-mr2a.r01 (1/2)",
+        Some("Here's your file!"),
+        None,
+        Some("abc-mr2a.r01"),
+        false,
+        counter(1, 2),
+        None
+    )]
+    #[case(
+        r#"My Upload - [01/10] - "Show (1/2) Part.mkv" yEnc (01/15) 1234"#,
+        Some("My Upload"),
+        counter(1, 10),
+        Some("Show (1/2) Part.mkv"),
+        true,
+        counter(1, 15),
+        Some(1234)
+    )]
+    #[case(
+        r#""Show (1/2) Part.mkv" yEnc"#,
+        None,
+        None,
+        Some("Show (1/2) Part.mkv"),
+        true,
+        None,
+        None
+    )]
+    #[case(
+        r#"(3/7) "Big Buck Bunny - S01E01.mkv" 16981056"#,
+        None,
+        counter(3, 7),
+        Some("Big Buck Bunny - S01E01.mkv"),
+        false,
+        None,
+        Some(16981056)
+    )]
+    #[case(
+        "[1/5] - abcdef yEnc (1/24) 1000",
+        None,
+        counter(1, 5),
+        Some("abcdef"),
+        true,
+        counter(1, 24),
+        Some(1000)
+    )]
+    #[case("abcdef yEnc (1/24) 1000", None, None, None, true, counter(1, 24), Some(1000))]
+    #[case("abc.rar (1/0)", None, None, Some("abc.rar"), false, counter(1, 0), None)]
+    #[case("abc.rar (a/2)", None, None, Some("abc.rar"), false, None, None)]
+    #[case("abc.rar (1/2", None, None, Some("abc.rar"), false, None, None)]
+    #[case("", None, None, None, false, None, None)]
+    fn test_subject_parse(
+        #[case] subject: &str,
+        #[case] prefix: Option<&str>,
+        #[case] file_counter: Option<SubjectCounter>,
+        #[case] file_name: Option<&str>,
+        #[case] is_yenc: bool,
+        #[case] segment_counter: Option<SubjectCounter>,
+        #[case] size: Option<u64>,
+    ) {
+        let parsed = Subject::parse(subject);
+        assert_eq!(parsed.as_str(), subject);
+        assert_eq!(parsed.prefix(), prefix);
+        assert_eq!(parsed.file_counter(), file_counter);
+        assert_eq!(parsed.file_name(), file_name);
+        assert_eq!(parsed.is_yenc(), is_yenc);
+        assert_eq!(parsed.segment_counter(), segment_counter);
+        assert_eq!(parsed.size(), size);
+    }
+
+    #[rstest]
+    #[case(r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#, Some(24))]
+    #[case(
+        r#"[011/116] - [Foobar] Violet Evergarden - 01.mkv yEnc (1/2401) 1720916370"#,
+        Some(2401)
+    )]
+    #[case("Here's your file!  abc-mr2a.r01 (1/2)", Some(2))]
+    #[case(r#""Show (1/2) Part.mkv" yEnc (01/15) 1234"#, Some(15))]
+    // A counter inside the filename is not the segment counter.
+    #[case(r#""Show (1/2) Part.mkv" yEnc"#, None)]
+    #[case(r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc"#, None)]
+    #[case("abc.rar (1/4000000000)", Some(4_000_000_000))]
+    #[case("abc.rar (1/0)", Some(0))]
+    #[case("abc.rar (a/2)", None)]
+    #[case("abc.rar (1/2", None)]
+    #[case("abc.rar (1/99999999999)", None)]
+    #[case("", None)]
+    fn test_segment_counter_total(#[case] subject: &str, #[case] expected: Option<u32>) {
+        assert_eq!(
+            Subject::parse(subject).segment_counter().map(|counter| counter.total),
+            expected
+        );
+    }
 }
@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::file::File;
+use crate::meta::Meta;
+use crate::nzb::Nzb;
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+const NZB_DOCTYPE: &str =
+    r#"<!DOCTYPE nzb PUBLIC "-//newzBin//DTD NZB 1.1//EN" "http://www.newzbin.com/DTD/nzb/nzb-1.1.dtd">"#;
+const NZB_NAMESPACE: &str = "http://www.newzbin.com/DTD/2003/nzb";
+const INDENT: &str = "    ";
+
+/// Return [`true`] if the character is allowed in an XML 1.0 document, [`false`] otherwise.
+///
+/// See the [`Char` production](https://www.w3.org/TR/xml/#NT-Char) of the XML 1.0 specification.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
+
+/// Escapes the characters that are not allowed verbatim in XML text or
+/// attribute values.
+///
+/// Tabs and line breaks are written as character references, since XML parsers normalize
+/// them to spaces in attribute values, and carriage returns to line feeds everywhere.
+/// Characters that XML 1.0 does not allow at all, such as most control characters, are dropped.
+///
+/// Returns the input unchanged (and without allocating) if there is nothing
+/// to escape.
+fn escape(s: &str) -> Cow<'_, str> {
+    let needs_escaping = |c: char| matches!(c, '&' | '<' | '>' | '"' | '\'' | '\t' | '\n' | '\r') || !is_xml_char(c);
+
+    if !s.contains(needs_escaping) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len() + 16);
+    for char in s.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            _ if !is_xml_char(char) => {}
+            _ => escaped.push(char),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Writes the `<head>` element, or nothing at all if there is no metadata.
+fn write_meta(meta: &Meta, w: &mut impl Write) -> io::Result<()> {
+    if meta.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(w, "{INDENT}<head>")?;
+    for (typ, value) in meta.iter() {
+        writeln!(
+            w,
+            "{INDENT}{INDENT}<meta type=\"{}\">{}</meta>",
+            escape(typ),
+            escape(value)
+        )?;
+    }
+    writeln!(w, "{INDENT}</head>")
+}
+
+/// Writes a single `<file>` element along with its groups and segments.
+fn write_file(file: &File, w: &mut impl Write) -> io::Result<()> {
+    writeln!(
+        w,
+        "{INDENT}<file poster=\"{}\" date=\"{}\" subject=\"{}\">",
+        escape(file.poster()),
+        file.posted_at().timestamp(),
+        escape(file.subject())
+    )?;
+
+    writeln!(w, "{INDENT}{INDENT}<groups>")?;
+    for group in file.groups() {
+        writeln!(w, "{INDENT}{INDENT}{INDENT}<group>{}</group>", escape(group))?;
+    }
+    writeln!(w, "{INDENT}{INDENT}</groups>")?;
+
+    writeln!(w, "{INDENT}{INDENT}<segments>")?;
+    for segment in file.segments() {
+        writeln!(
+            w,
+            "{INDENT}{INDENT}{INDENT}<segment bytes=\"{}\" number=\"{}\">{}</segment>",
+            segment.size(),
+            segment.number(),
+            escape(segment.message_id())
+        )?;
+    }
+    writeln!(w, "{INDENT}{INDENT}</segments>")?;
+
+    writeln!(w, "{INDENT}</file>")
+}
+
+/// Serializes an [`Nzb`] into an NZB 1.1 document.
+///
+/// The output always starts with an XML declaration and the NZB 1.1 DOCTYPE,
+/// and uses the `http://www.newzbin.com/DTD/2003/nzb` namespace as described
+/// in the [NZB specification][0]. The `<head>` element is omitted when the
+/// [`Meta`] is empty.
+///
+/// [0]: <https://sabnzbd.org/wiki/extra/nzb-spec>
+pub(crate) fn write_nzb(nzb: &Nzb, w: &mut impl Write) -> io::Result<()> {
+    writeln!(w, "{XML_DECLARATION}")?;
+    writeln!(w, "{NZB_DOCTYPE}")?;
+    writeln!(w, "<nzb xmlns=\"{NZB_NAMESPACE}\">")?;
+
+    write_meta(nzb.meta(), w)?;
+
+    for file in nzb.files() {
+        write_file(file, w)?;
+    }
+
+    writeln!(w, "</nzb>")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case("", "")]
+    #[case("Joe Bloggs <bloggs@nowhere.example>", "Joe Bloggs &lt;bloggs@nowhere.example&gt;")]
+    #[case(r#"[1/5] - "file.mkv" yEnc (1/24)"#, "[1/5] - &quot;file.mkv&quot; yEnc (1/24)")]
+    #[case("Tom & Jerry's", "Tom &amp; Jerry&apos;s")]
+    #[case("&amp;", "&amp;amp;")]
+    #[case("one\ttwo\r\nthree", "one&#9;two&#13;&#10;three")]
+    #[case("bell\u{7}\u{1}\u{FFFE}", "bell")]
+    #[case("é \u{E000} \u{1F600}", "é \u{E000} \u{1F600}")]
+    fn test_escape(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(escape(input), expected);
+    }
+
+    #[test]
+    fn test_escape_borrows_when_unchanged() {
+        assert!(matches!(escape("nothing to see here"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_write_meta_omits_empty_head() {
+        let mut out = Vec::new();
+        write_meta(&Meta::default(), &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_write_meta_order() {
+        let meta = Meta::new(Some("Title"), ["one", "two"], ["HD"], Some("TV"));
+        let mut out = Vec::new();
+        write_meta(&meta, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            [
+                "    <head>",
+                "        <meta type=\"title\">Title</meta>",
+                "        <meta type=\"password\">one</meta>",
+                "        <meta type=\"password\">two</meta>",
+                "        <meta type=\"tag\">HD</meta>",
+                "        <meta type=\"category\">TV</meta>",
+                "    </head>",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_write_meta_keeps_document_order_and_unknown_types() {
+        let meta = [
+            ("x-dnzb-imdb", "tt1727587"),
+            ("Title", "Sintel"),
+            ("x-dnzb-name", "A & B"),
+        ]
+        .into_iter()
+        .collect::<Meta>();
+        let mut out = Vec::new();
+        write_meta(&meta, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            [
+                "    <head>",
+                "        <meta type=\"x-dnzb-imdb\">tt1727587</meta>",
+                "        <meta type=\"Title\">Sintel</meta>",
+                "        <meta type=\"x-dnzb-name\">A &amp; B</meta>",
+                "    </head>",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
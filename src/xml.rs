@@ -1,41 +1,126 @@
+use crate::errors::{ParseNzbError, TextPosition};
+
 /// Removes the leading XML declaration and/or DOCTYPE from the input.
 ///
 /// This is intended for use with `roxmltree`, which does not support XML
 /// declarations or DOCTYPEs, and strips those constructs from the beginning
 /// of the document while leaving the rest unchanged.
-fn strip_headers(xml: &str) -> &str {
-    let mut s = xml.trim();
+///
+/// Returns the remaining text along with the byte offset it starts at in the input.
+fn strip_headers(xml: &str) -> (usize, &str) {
+    let xml = xml.trim_end();
+    let mut offset = xml.len() - xml.trim_start().len();
+
+    // Skips the construct starting with `open` (case-insensitive) up to and including `close`, if present.
+    let mut skip = |open: &str, close: &str| {
+        let s = &xml[offset..];
+        if s.get(..open.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(open))
+            && let Some(end) = s.find(close)
+        {
+            let rest = &s[end + close.len()..];
+            offset += s.len() - rest.trim_start().len();
+        }
+    };
 
     // Strip XML declaration: <?xml ... ?>
-    if s.len() >= 5
-        && s[..5].eq_ignore_ascii_case("<?xml")
-        && let Some(end) = s.find("?>")
-    {
-        s = s[end + 2..].trim_start();
+    skip("<?xml", "?>");
+    // Strip DOCTYPE: <!DOCTYPE ... >
+    skip("<!DOCTYPE", ">");
+
+    (offset, &xml[offset..])
+}
+
+/// Where the text handed to `roxmltree` starts within the original input,
+/// used to translate the positions it reports back to the original input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Origin {
+    /// Number of lines before the first line of the stripped text.
+    lines: u32,
+    /// Number of characters before the stripped text on its first line.
+    columns: u32,
+}
+
+impl Origin {
+    /// Computes the origin of the text that starts at byte `offset` in `xml`.
+    fn new(xml: &str, offset: usize) -> Self {
+        let prefix = &xml[..offset];
+        let last_line = prefix.rfind('\n').map_or(prefix, |newline| &prefix[newline + 1..]);
+
+        Self {
+            lines: u32::try_from(prefix.matches('\n').count()).unwrap_or(u32::MAX),
+            columns: u32::try_from(last_line.chars().count()).unwrap_or(u32::MAX),
+        }
     }
 
-    // Strip DOCTYPE: <!DOCTYPE ... >
-    if s.len() >= 9
-        && s[..9].eq_ignore_ascii_case("<!DOCTYPE")
-        && let Some(end) = s.find('>')
-    {
-        s = s[end + 1..].trim_start();
+    fn translate(self, position: roxmltree::TextPos) -> TextPosition {
+        if position.row == 1 {
+            TextPosition {
+                line: self.lines + 1,
+                column: self.columns + position.col,
+            }
+        } else {
+            TextPosition {
+                line: self.lines + position.row,
+                column: position.col,
+            }
+        }
+    }
+}
+
+/// A parsed NZB document that remembers where it came from in the original input.
+pub(crate) struct Document<'input> {
+    document: roxmltree::Document<'input>,
+    origin: Origin,
+}
+
+impl<'input> Document<'input> {
+    /// The underlying `roxmltree` document.
+    pub(crate) fn roxmltree(&self) -> &roxmltree::Document<'input> {
+        &self.document
     }
 
-    s
+    /// Returns the position of the start of `node` within the original input.
+    pub(crate) fn position(&self, node: roxmltree::Node) -> TextPosition {
+        self.origin.translate(self.document.text_pos_at(node.range().start))
+    }
 }
 
 /// Thin wrapper around `roxmltree::Document::parse` that strips unsupported
 /// XML declarations and DOCTYPEs before parsing.
-pub(crate) fn parse_document(xml: &str) -> Result<roxmltree::Document<'_>, roxmltree::Error> {
-    let stripped = strip_headers(xml);
+///
+/// Positions, both in the returned error and from [`Document::position`],
+/// refer to the original input rather than the stripped one.
+pub(crate) fn parse_document(xml: &str) -> Result<Document<'_>, ParseNzbError> {
+    let (offset, stripped) = strip_headers(xml);
+    let origin = Origin::new(xml, offset);
+
     roxmltree::Document::parse(stripped)
+        .map(|document| Document { document, origin })
+        .map_err(|error| syntax_error(&error, origin))
+}
+
+/// Converts a `roxmltree` error into a [`ParseNzbError::XmlSyntax`], with its position
+/// translated to the original input. Errors that are not tied to a position have none.
+fn syntax_error(error: &roxmltree::Error, origin: Origin) -> ParseNzbError {
+    let message = error.to_string();
+    let position = error.pos();
+
+    // roxmltree includes the position in its messages, but relative to the stripped input,
+    // so it is removed from the message and reported separately. Errors without a position
+    // do not mention one.
+    let at = format!(" at {position}");
+    match message.find(&at) {
+        Some(start) => {
+            let message = format!("{}{}", &message[..start], &message[start + at.len()..]);
+            ParseNzbError::xml_syntax(message, Some(origin.translate(position)))
+        }
+        None => ParseNzbError::xml_syntax(message, None),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use roxmltree::Document;
-
     use super::*;
 
     #[test]
@@ -84,7 +169,10 @@ mod tests {
         </nzb>
         "#.trim();
 
-        assert_eq!(strip_headers(original), stripped)
+        assert_eq!(strip_headers(original).1, stripped);
+        assert_eq!(strip_headers("  <?XML?>\n<nzb/> "), (10, "<nzb/>"));
+        assert_eq!(strip_headers("<?xml"), (0, "<?xml"));
+        assert_eq!(strip_headers("é<nzb/>"), (0, "é<nzb/>"));
     }
 
     #[test]
@@ -133,21 +221,74 @@ mod tests {
         </nzb>
         "#.trim();
 
-        assert!(Document::parse(nzb).is_err());
-        assert!(Document::parse(control).is_ok());
+        assert!(roxmltree::Document::parse(nzb).is_err());
+        assert!(roxmltree::Document::parse(control).is_ok());
         assert!(parse_document(nzb).is_ok());
         assert!(parse_document(control).is_ok());
 
         let subjects = vec!["Here's your file!  abc-mr2a.r01 (1/2)"];
 
-        fn extract_subjects(doc: &Document) -> Vec<String> {
+        fn extract_subjects(doc: &roxmltree::Document) -> Vec<String> {
             doc.descendants()
                 .filter(|n| n.has_tag_name("file"))
                 .filter_map(|n| n.attribute("subject").map(String::from))
                 .collect()
         }
 
-        assert_eq!(extract_subjects(&Document::parse(control).unwrap()), subjects);
-        assert_eq!(extract_subjects(&parse_document(control).unwrap()), subjects);
+        assert_eq!(
+            extract_subjects(&roxmltree::Document::parse(control).unwrap()),
+            subjects
+        );
+        assert_eq!(extract_subjects(parse_document(control).unwrap().roxmltree()), subjects);
+    }
+
+    #[test]
+    fn test_positions_refer_to_original_input() {
+        let nzb = "\n  <?xml version=\"1.0\"?> <!DOCTYPE nzb>  <nzb>\n    <file/>\n</nzb>";
+        let document = parse_document(nzb).unwrap();
+        let root = document.roxmltree().root_element();
+        let file = root.first_element_child().unwrap();
+
+        assert_eq!(document.position(root), TextPosition { line: 2, column: 41 });
+        assert_eq!(document.position(file), TextPosition { line: 3, column: 5 });
+    }
+
+    #[test]
+    fn test_syntax_error_positions() {
+        let nzb = "<?xml version=\"1.0\"?>\n<nzb>\n  <file>\n</nzb>";
+        assert_eq!(
+            parse_document(nzb).err(),
+            Some(ParseNzbError::xml_syntax(
+                "expected 'file' tag, not 'nzb'",
+                Some(TextPosition { line: 4, column: 1 })
+            ))
+        );
+
+        let nzb = "<?xml version=\"1.0\"?> <nzb> <file> </nzb>";
+        assert_eq!(
+            parse_document(nzb).err(),
+            Some(ParseNzbError::xml_syntax(
+                "expected 'file' tag, not 'nzb'",
+                Some(TextPosition { line: 1, column: 36 })
+            ))
+        );
+
+        // The position is removed from the middle of the message too.
+        let nzb = "<?xml version=\"1.0\"?>\n<nzb a=\"1\" a=\"2\"/>";
+        assert_eq!(
+            parse_document(nzb).err(),
+            Some(ParseNzbError::xml_syntax(
+                "attribute 'a' is already defined",
+                Some(TextPosition { line: 2, column: 12 })
+            ))
+        );
+
+        assert_eq!(
+            parse_document("<?xml version=\"1.0\"?>").err(),
+            Some(ParseNzbError::xml_syntax(
+                "the document does not have a root node",
+                None
+            ))
+        );
     }
 }
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::PathBuf;
+
+use nzb_rs::{Diagnostic, DiagnosticKind, Nzb, ParseNzbError};
+use rstest::rstest;
+
+fn get_file(name: &str) -> PathBuf {
+    PathBuf::new()
+        .join(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("nzbs")
+        .join(name)
+}
+
+const BIG_BUCK_BUNNY: &str = r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#;
+
+#[test]
+fn test_lenient_reports_bad_segments() {
+    let xml = fs::read_to_string(get_file("valid_nzb_with_bad_segments.nzb")).unwrap();
+    let (nzb, diagnostics) = Nzb::parse_lenient(&xml).unwrap();
+
+    assert_eq!(nzb, Nzb::parse(&xml).unwrap());
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.file_index(), d.subject(), d.element_index(), d.kind()))
+            .collect::<Vec<_>>(),
+        vec![
+            (4, BIG_BUCK_BUNNY, 10, DiagnosticKind::SegmentBytes),
+            (4, BIG_BUCK_BUNNY, 11, DiagnosticKind::SegmentNumber),
+            (4, BIG_BUCK_BUNNY, 12, DiagnosticKind::SegmentMessageId),
+        ]
+    );
+}
+
+#[test]
+fn test_strict_rejects_bad_segments() {
+    let xml = fs::read_to_string(get_file("valid_nzb_with_bad_segments.nzb")).unwrap();
+    let (_, diagnostics) = Nzb::parse_lenient(&xml).unwrap();
+
+    assert_eq!(
+        Nzb::parse_strict(&xml),
+        Err(ParseNzbError::Diagnostic(diagnostics[0].clone()))
+    );
+}
+
+#[rstest]
+#[case::spec_example(get_file("spec_example.nzb"))]
+#[case::big_buck_bunny(get_file("big_buck_bunny.nzb"))]
+#[case::multi_rar(get_file("multi_rar.nzb"))]
+fn test_clean_nzbs(#[case] nzb_file: PathBuf) {
+    let xml = fs::read_to_string(nzb_file).unwrap();
+    let nzb = Nzb::parse(&xml).unwrap();
+
+    assert_eq!(Nzb::parse_lenient(&xml).unwrap(), (nzb.clone(), Vec::new()));
+    assert_eq!(Nzb::parse_strict(&xml).unwrap(), nzb);
+}
+
+#[test]
+fn test_empty_groups() {
+    let xml = r#"
+        <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+            <file poster="Joe Bloggs &lt;bloggs@nowhere.example&gt;" date="1071674882" subject="abc.rar">
+                <groups>
+                    <group></group>
+                    <group>alt.binaries.newzbin</group>
+                    <group/>
+                </groups>
+                <segments>
+                    <segment bytes="102394" number="1">123456789abcdef@news.newzbin.com</segment>
+                </segments>
+            </file>
+        </nzb>
+    "#;
+
+    let (nzb, diagnostics) = Nzb::parse_lenient(xml).unwrap();
+    assert_eq!(nzb.file().groups(), ["alt.binaries.newzbin"]);
+    assert_eq!(
+        diagnostics.iter().map(Diagnostic::element_index).collect::<Vec<_>>(),
+        vec![0, 2]
+    );
+    assert!(diagnostics.iter().all(|d| d.kind() == DiagnosticKind::EmptyGroup));
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "empty or missing name in a 'group' element (file #0 'abc.rar', group #0)"
+    );
+
+    match Nzb::parse_strict(xml).unwrap_err() {
+        ParseNzbError::Diagnostic(diagnostic) => assert_eq!(diagnostic, diagnostics[0]),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_whitespace_only_groups_are_kept() {
+    // Only built or edited files have their blank groups and Message-IDs discarded.
+    let xml = r#"
+        <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+            <file poster="Joe" date="1071674882" subject="abc.rar">
+                <groups><group> </group></groups>
+                <segments><segment bytes="102394" number="1"> </segment></segments>
+            </file>
+        </nzb>
+    "#;
+
+    let (nzb, diagnostics) = Nzb::parse_lenient(xml).unwrap();
+    assert!(diagnostics.is_empty());
+    assert_eq!(nzb.file().groups(), [" "]);
+    assert_eq!(nzb.file().segments()[0].message_id(), " ");
+    assert_eq!(Nzb::parse_strict(xml).unwrap(), nzb);
+}
+
+#[test]
+fn test_hard_errors_are_unchanged() {
+    let xml = fs::read_to_string(get_file("malformed_segments.nzb")).unwrap();
+    assert_eq!(Nzb::parse_lenient(&xml).unwrap_err(), Nzb::parse(&xml).unwrap_err());
+}
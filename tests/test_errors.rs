@@ -1,7 +1,7 @@
 use std::io;
 use std::path::{Path, PathBuf};
 
-use nzb_rs::{FileAttributeKind, Nzb, ParseNzbError, ParseNzbFileError};
+use nzb_rs::{FileAttributeKind, Nzb, ParseNzbError, ParseNzbFileError, ParseNzbReaderError, TextPosition};
 
 fn get_file(name: &str) -> PathBuf {
     PathBuf::new()
@@ -39,7 +39,10 @@ fn test_invalid_xml() {
     assert!(nzb.is_err());
     assert_eq!(
         nzb.unwrap_err(),
-        ParseNzbError::XmlSyntax("the root node was opened but never closed".to_string())
+        ParseNzbError::XmlSyntax {
+            message: "the root node was opened but never closed".to_string(),
+            position: None
+        }
     )
 }
 
@@ -63,7 +66,44 @@ fn test_valid_xml_but_invalid_nzb() {
 
     let nzb = Nzb::parse(valid_xml_but_invalid_nzb);
     assert!(nzb.is_err());
-    assert_eq!(nzb.unwrap_err(), ParseNzbError::SegmentsElement)
+
+    match nzb.unwrap_err() {
+        ParseNzbError::SegmentsElement { location } => {
+            assert_eq!(location.index(), Some(0));
+            assert_eq!(location.subject(), Some("Here's your file!  abc-mr2a.r01 (1/2)"));
+            assert_eq!(location.position(), Some(TextPosition { line: 8, column: 9 }));
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_syntax_error_position() {
+    let invalid_xml = r#"
+    <?xml version="1.0" encoding="iso-8859-1" ?>
+    <!DOCTYPE nzb PUBLIC "-//newzBin//DTD NZB 1.1//EN" "http://www.newzbin.com/DTD/nzb/nzb-1.1.dtd">
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <file poster="Joe Bloggs &lt;bloggs@nowhere.example&gt;" date="1071674882" subject="abc-mr2a.r01 (1/2)">
+            <groups>
+                <group>alt.binaries.newzbin</groups>
+            </groups>
+        </file>
+    </nzb>
+    "#;
+
+    let error = Nzb::parse(invalid_xml).unwrap_err();
+    assert_eq!(
+        error,
+        ParseNzbError::XmlSyntax {
+            message: "expected 'group' tag, not 'groups'".to_string(),
+            position: Some(TextPosition { line: 7, column: 44 })
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "The NZB document is not valid XML and could not be parsed: \
+        expected 'group' tag, not 'groups' at line 7, column 44"
+    );
 }
 
 #[test]
@@ -92,7 +132,7 @@ fn test_malformed_files2() {
 
     match error {
         ParseNzbFileError::Parse { source } => {
-            assert_eq!(source, ParseNzbError::GroupsElement)
+            assert!(matches!(source, ParseNzbError::GroupsElement { .. }))
         }
         _ => panic!(),
     }
@@ -108,7 +148,7 @@ fn test_malformed_groups() {
 
     match error {
         ParseNzbFileError::Parse { source } => {
-            assert_eq!(source, ParseNzbError::GroupsElement)
+            assert!(matches!(source, ParseNzbError::GroupsElement { .. }))
         }
         _ => panic!(),
     }
@@ -124,7 +164,7 @@ fn test_malformed_segments() {
 
     match error {
         ParseNzbFileError::Parse { source } => {
-            assert_eq!(source, ParseNzbError::SegmentsElement)
+            assert!(matches!(source, ParseNzbError::SegmentsElement { .. }))
         }
         _ => panic!(),
     }
@@ -156,9 +196,121 @@ fn test_bad_nzb_file() {
     let error = nzb.unwrap_err();
 
     match error {
-        ParseNzbFileError::Io { source, file } => {
-            assert_eq!(source.kind(), io::ErrorKind::InvalidData);
-            assert_eq!(file, get_file("invalid_bytes.nzb"));
+        ParseNzbFileError::Parse { source } => {
+            assert_eq!(source, ParseNzbError::InvalidEncoding("UTF-8".to_string()));
+            assert_eq!(source.to_string(), "The NZB document is not valid UTF-8");
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_bad_nzb_bytes() {
+    let bytes = std::fs::read(get_file("invalid_bytes.nzb")).unwrap();
+
+    match Nzb::from_bytes(&bytes).unwrap_err() {
+        ParseNzbReaderError::Parse { source } => {
+            assert_eq!(source, ParseNzbError::InvalidEncoding("UTF-8".to_string()));
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_unsupported_encoding() {
+    let file = get_file("unsupported_encoding.nzb");
+    let expected = ParseNzbError::UnsupportedEncoding("ebcdic-cp-us".to_string());
+
+    match Nzb::parse_file(&file).unwrap_err() {
+        ParseNzbFileError::Parse { source } => assert_eq!(source, expected),
+        _ => panic!(),
+    }
+
+    match Nzb::from_bytes(std::fs::read(&file).unwrap()).unwrap_err() {
+        ParseNzbReaderError::Parse { source } => assert_eq!(source, expected),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_malformed_utf16_bytes() {
+    // A UTF-16LE byte order mark followed by an unpaired surrogate.
+    match Nzb::from_bytes(b"\xFF\xFE<\0\x00\xD8").unwrap_err() {
+        ParseNzbReaderError::Parse { source } => {
+            assert_eq!(source, ParseNzbError::InvalidEncoding("UTF-16LE".to_string()));
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_truncated_gzip_bytes() {
+    let bytes = std::fs::read(get_file("spec_example.nzb.gz")).unwrap();
+
+    match Nzb::from_bytes(&bytes[..bytes.len() / 2]).unwrap_err() {
+        ParseNzbReaderError::Gzip { source } => {
+            assert_eq!(source.kind(), io::ErrorKind::UnexpectedEof);
+        }
+        _ => panic!(),
+    }
+}
+
+#[cfg(feature = "bzip2")]
+#[test]
+fn test_truncated_bzip2_bytes() {
+    let bytes = std::fs::read(get_file("spec_example.nzb.bz2")).unwrap();
+
+    match Nzb::from_bytes(&bytes[..bytes.len() / 2]).unwrap_err() {
+        ParseNzbReaderError::Bzip2 { source } => {
+            assert_eq!(source.kind(), io::ErrorKind::UnexpectedEof);
+        }
+        _ => panic!(),
+    }
+}
+
+#[cfg(feature = "xz")]
+#[test]
+fn test_truncated_xz_bytes() {
+    let bytes = std::fs::read(get_file("spec_example.nzb.xz")).unwrap();
+
+    match Nzb::from_bytes(&bytes[..bytes.len() / 2]).unwrap_err() {
+        ParseNzbReaderError::Xz { .. } => {}
+        _ => panic!(),
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_truncated_zstd_bytes() {
+    let bytes = std::fs::read(get_file("spec_example.nzb.zst")).unwrap();
+
+    match Nzb::from_bytes(&bytes[..bytes.len() / 2]).unwrap_err() {
+        ParseNzbReaderError::Zstd { .. } => {}
+        _ => panic!(),
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_bad_zstd_file() {
+    // The extension takes precedence over the contents.
+    let file = get_file("spec_example.nzb");
+    let copy = Path::new(env!("CARGO_TARGET_TMPDIR")).join("not_zstd.nzb.zst");
+    std::fs::copy(&file, &copy).unwrap();
+
+    match Nzb::parse_file(&copy).unwrap_err() {
+        ParseNzbFileError::Zstd { file, .. } => assert_eq!(file, copy),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_malformed_nzb_from_reader() {
+    let file = std::fs::File::open(get_file("malformed_segments.nzb")).unwrap();
+
+    match Nzb::from_reader(file).unwrap_err() {
+        ParseNzbReaderError::Parse { source } => {
+            assert!(matches!(source, ParseNzbError::SegmentsElement { .. }))
         }
         _ => panic!(),
     }
@@ -180,6 +332,19 @@ fn test_non_existent_file() {
     }
 }
 
+/// Checks that `error` is about the `expected` attribute of the only file in the `test_file_with_*` NZBs below.
+fn assert_file_attribute(error: &ParseNzbError, expected: FileAttributeKind, subject: Option<&str>) {
+    match error {
+        ParseNzbError::FileAttribute { kind, location } => {
+            assert_eq!(*kind, expected);
+            assert_eq!(location.index(), Some(0));
+            assert_eq!(location.subject(), subject);
+            assert_eq!(location.position(), Some(TextPosition { line: 10, column: 9 }));
+        }
+        _ => panic!(),
+    }
+}
+
 #[test]
 fn test_file_with_missing_poster() {
     let no_poster = r#"
@@ -207,9 +372,10 @@ fn test_file_with_missing_poster() {
     .trim();
 
     let nzb = Nzb::parse(no_poster);
-    assert_eq!(
-        nzb.unwrap_err(),
-        ParseNzbError::FileAttribute(FileAttributeKind::Poster)
+    assert_file_attribute(
+        &nzb.unwrap_err(),
+        FileAttributeKind::Poster,
+        Some("Here's your file!  abc-mr2a.r01 (1/2)"),
     );
 }
 
@@ -239,7 +405,11 @@ fn test_file_with_bad_date() {
     "#.trim();
 
     let nzb = Nzb::parse(bad_date);
-    assert_eq!(nzb.unwrap_err(), ParseNzbError::FileAttribute(FileAttributeKind::Date));
+    assert_file_attribute(
+        &nzb.unwrap_err(),
+        FileAttributeKind::Date,
+        Some("Here's your file!  abc-mr2a.r01 (1/2)"),
+    );
 }
 
 #[test]
@@ -269,9 +439,11 @@ fn test_file_with_missing_subject() {
     .trim();
 
     let nzb = Nzb::parse(no_subject);
+    let error = nzb.unwrap_err();
+    assert_file_attribute(&error, FileAttributeKind::Subject, None);
     assert_eq!(
-        nzb.unwrap_err(),
-        ParseNzbError::FileAttribute(FileAttributeKind::Subject)
+        error.to_string(),
+        "Invalid or missing required attribute 'subject' in 'file' element #0 at line 10, column 9."
     );
 }
 
@@ -1,7 +1,11 @@
+use std::fs;
+use std::ops::RangeInclusive;
 use std::{path::PathBuf, vec};
 
 use chrono::DateTime;
-use nzb_rs::{File, Nzb, Segment};
+use nzb_rs::{
+    File, FileKind, Meta, Nzb, NzbBuilder, ObfuscationRule, Par2Volume, ParseNzbError, Segment, SubjectCounter,
+};
 use rstest::rstest;
 
 fn get_file(name: &str) -> PathBuf {
@@ -15,6 +19,9 @@ fn get_file(name: &str) -> PathBuf {
 #[rstest]
 #[case::spec_example_nzb(get_file("spec_example.nzb"))]
 #[case::spec_example_nzb_gz(get_file("spec_example.nzb.gz"))]
+#[cfg_attr(feature = "bzip2", case::spec_example_nzb_bz2(get_file("spec_example.nzb.bz2")))]
+#[cfg_attr(feature = "xz", case::spec_example_nzb_xz(get_file("spec_example.nzb.xz")))]
+#[cfg_attr(feature = "zstd", case::spec_example_nzb_zst(get_file("spec_example.nzb.zst")))]
 fn test_spec_example(#[case] nzb_file: PathBuf) {
     let nzb = Nzb::parse_file(nzb_file).unwrap();
     assert_eq!(nzb.meta().title(), Some("Your File!"));
@@ -24,8 +31,18 @@ fn test_spec_example(#[case] nzb_file: PathBuf) {
     assert_eq!(nzb.files().len(), 1);
     assert!(nzb.is_rar());
     assert!(nzb.is_obfuscated());
+    assert!(nzb.file().obfuscation().is_obfuscated());
+    assert_eq!(nzb.file().obfuscation().rule(), &ObfuscationRule::Fallback);
     assert_eq!(nzb.file().name(), Some("abc-mr2a.r01"));
     assert_eq!(nzb.file().stem(), Some("abc-mr2a"));
+    assert_eq!(nzb.file().parsed_subject().prefix(), Some("Here's your file!"));
+    assert_eq!(nzb.file().parsed_subject().file_name(), nzb.file().name());
+    assert_eq!(nzb.file().parsed_subject().file_counter(), None);
+    assert!(!nzb.file().parsed_subject().is_yenc());
+    assert_eq!(nzb.file().expected_segment_count(), Some(2));
+    assert!(nzb.file().missing_segments().is_empty());
+    assert!(nzb.file().is_complete());
+    assert!(nzb.is_complete());
     assert_eq!(nzb.file().extension(), Some("r01"));
     assert!(nzb.file().has_extension("r01"));
     assert!(nzb.file().has_extension(".R01"));
@@ -52,6 +69,39 @@ fn test_spec_example(#[case] nzb_file: PathBuf) {
     );
 }
 
+#[rstest]
+#[case::spec_example_nzb(get_file("spec_example.nzb"))]
+#[case::spec_example_nzb_gz(get_file("spec_example.nzb.gz"))]
+#[case::gzipped_without_gz_extension(get_file("gzipped_without_gz_extension.nzb"))]
+#[cfg_attr(feature = "bzip2", case::spec_example_nzb_bz2(get_file("spec_example.nzb.bz2")))]
+#[cfg_attr(feature = "xz", case::spec_example_nzb_xz(get_file("spec_example.nzb.xz")))]
+#[cfg_attr(feature = "zstd", case::spec_example_nzb_zst(get_file("spec_example.nzb.zst")))]
+fn test_compression_sniffing(#[case] nzb_file: PathBuf) {
+    let expected = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let bytes = fs::read(&nzb_file).unwrap();
+
+    assert_eq!(Nzb::parse_file(&nzb_file).unwrap(), expected);
+    assert_eq!(Nzb::from_bytes(&bytes).unwrap(), expected);
+    assert_eq!(Nzb::from_reader(&bytes[..]).unwrap(), expected);
+    assert_eq!(Nzb::from_reader(fs::File::open(&nzb_file).unwrap()).unwrap(), expected);
+}
+
+#[rstest]
+#[case::latin1(get_file("latin1_encoded.nzb"), "Café Crème")]
+#[case::windows1252(get_file("windows1252_encoded.nzb"), "“Your File” – €5")]
+#[case::utf16le(get_file("utf16le_encoded.nzb"), "Café ☕")]
+#[case::utf16be(get_file("utf16be_encoded.nzb"), "Café ☕")]
+fn test_non_utf8_encodings(#[case] nzb_file: PathBuf, #[case] title: &str) {
+    let bytes = fs::read(&nzb_file).unwrap();
+
+    for nzb in [Nzb::parse_file(&nzb_file).unwrap(), Nzb::from_bytes(&bytes).unwrap()] {
+        assert_eq!(nzb.meta().title(), Some(title));
+        assert_eq!(nzb.file().poster(), "Jöe Bloggs <bloggs@nowhere.example>");
+        assert_eq!(nzb.file().name(), Some("abc-mr2a.r01"));
+        assert_eq!(nzb.size(), 106_895);
+    }
+}
+
 #[rstest]
 #[case::big_buck_bunny_nzb(get_file("big_buck_bunny.nzb"))]
 #[case::big_buck_bunny_nzb_gz(get_file("big_buck_bunny.nzb.gz"))]
@@ -65,6 +115,7 @@ fn test_big_buck_bunny(#[case] nzb_file: PathBuf) {
     assert_eq!(nzb.files().len(), 5);
     assert!(!nzb.is_rar());
     assert!(!nzb.is_obfuscated());
+    assert_eq!(nzb.file().obfuscation().rule(), &ObfuscationRule::MixedCaseWords);
     assert!(nzb.has_par2());
     assert_eq!(nzb.size(), 22_704_889);
     assert_eq!(nzb.file().name(), Some("Big Buck Bunny - S01E01.mkv"));
@@ -207,6 +258,21 @@ fn test_valid_nzb_with_bad_segments(#[case] nzb_file: PathBuf) {
     assert_eq!(nzb.groups().collect::<Vec<_>>(), vec!["alt.binaries.boneless"]);
     assert_eq!(nzb.par2_size(), 5_183_128);
     assert_eq!(nzb.par2_percentage().floor(), 25.0);
+    assert!(!nzb.is_complete());
+    assert_eq!(
+        nzb.incomplete_files().map(|f| f.subject()).collect::<Vec<_>>(),
+        vec![r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#]
+    );
+    assert_eq!(nzb.file().expected_segment_count(), Some(24));
+    assert_eq!(
+        nzb.file().parsed_subject().file_counter(),
+        Some(SubjectCounter { current: 1, total: 5 })
+    );
+    assert_eq!(nzb.file().parsed_subject().size(), Some(16981056));
+    assert_eq!(nzb.file().missing_segments(), vec![11..=13]);
+    assert_eq!(nzb.file().missing_segment_count(), 3);
+    assert!(nzb.file().duplicate_segments().is_empty());
+    assert!(!nzb.file().is_complete());
     assert_eq!(
         nzb.file(),
         &File::new(
@@ -329,6 +395,16 @@ fn test_non_standard_meta() {
     assert_eq!(nzb.meta().category(), None);
     assert!(nzb.meta().passwords().is_empty());
     assert!(nzb.meta().tags().is_empty());
+    assert_eq!(nzb.meta().get("NAME"), Some("Your File!"));
+    assert_eq!(
+        nzb.meta().iter().collect::<Vec<_>>(),
+        vec![
+            ("name", "Your File!"),
+            ("badmeta1", "secret"),
+            ("badmeta2", "HD"),
+            ("badmeta3", "TV")
+        ]
+    );
 }
 
 #[test]
@@ -383,3 +459,640 @@ fn test_multi_rar() {
     assert!(nzb.is_rar());
     assert!(!nzb.has_par2());
 }
+
+#[rstest]
+#[case::complete("\"a.mkv\" yEnc (1/3)", vec![1, 2, 3], Some(3), vec![], vec![])]
+#[case::gap("\"a.mkv\" yEnc (1/4)", vec![1, 2, 4], Some(4), vec![3..=3], vec![])]
+#[case::gaps("\"a.mkv\" yEnc (1/9)", vec![2, 4, 5, 9], Some(9), vec![1..=1, 3..=3, 6..=8], vec![])]
+#[case::truncated("\"a.mkv\" yEnc (1/5)", vec![1, 2, 3], Some(5), vec![4..=5], vec![])]
+#[case::duplicates("\"a.mkv\" yEnc (1/3)", vec![3, 1, 3, 2, 3], Some(3), vec![], vec![3])]
+#[case::unsorted_duplicates("\"a.mkv\" yEnc (1/4)", vec![3, 1, 3, 2, 3], Some(4), vec![4..=4], vec![3])]
+#[case::out_of_range("\"a.mkv\" yEnc (1/2)", vec![1, 3, 4], Some(2), vec![2..=2], vec![])]
+#[case::no_counter("\"a.mkv\" yEnc", vec![1, 3, 1], None, vec![2..=2], vec![1])]
+fn test_segment_completeness(
+    #[case] subject: &str,
+    #[case] numbers: Vec<u32>,
+    #[case] expected: Option<u32>,
+    #[case] missing: Vec<RangeInclusive<u32>>,
+    #[case] duplicates: Vec<u32>,
+) {
+    let segments = numbers
+        .into_iter()
+        .map(|number| Segment::new(1024, number, format!("{number}@example")));
+    let file = File::new("poster", DateTime::UNIX_EPOCH, subject, ["alt.binaries.test"], segments);
+
+    assert_eq!(file.expected_segment_count(), expected);
+    assert_eq!(file.missing_segments(), missing);
+    assert_eq!(file.duplicate_segments(), duplicates);
+    assert_eq!(file.is_complete(), missing.is_empty());
+}
+
+#[test]
+fn test_segment_completeness_huge_counter() {
+    // The counter comes straight from the subject, so it must not decide how much memory is used.
+    let segments = [Segment::new(1024, 1, "1@example"), Segment::new(1024, 3, "3@example")];
+    let file = File::new(
+        "poster",
+        DateTime::UNIX_EPOCH,
+        "\"a.mkv\" yEnc (1/4000000000)",
+        ["alt.binaries.test"],
+        segments,
+    );
+
+    assert_eq!(file.expected_segment_count(), Some(4_000_000_000));
+    assert!(!file.is_complete());
+    assert_eq!(file.missing_segments(), vec![2..=2, 4..=4_000_000_000]);
+    assert_eq!(file.missing_segment_count(), 3_999_999_998);
+
+    let nzb = NzbBuilder::new().file(file).build().unwrap();
+    assert!(!nzb.is_complete());
+    assert_eq!(nzb.incomplete_files().count(), 1);
+}
+
+#[test]
+fn test_segment_completeness_highest_number() {
+    // Without a counter, the highest segment number present is the last one, even if it is `u32::MAX`.
+    let xml = r#"
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <file poster="Joe" date="1071674882" subject="&quot;a.mkv&quot; yEnc">
+            <groups><group>alt.binaries.test</group></groups>
+            <segments>
+                <segment bytes="1024" number="1">1@example</segment>
+                <segment bytes="1024" number="4294967295">4294967295@example</segment>
+            </segments>
+        </file>
+        <file poster="Joe" date="1071674882" subject="&quot;a.mkv.vol00+01.par2&quot; yEnc (1/1) 1024">
+            <groups><group>alt.binaries.test</group></groups>
+            <segments><segment bytes="1024" number="1">par2@example</segment></segments>
+        </file>
+    </nzb>
+    "#;
+    let nzb = Nzb::parse(xml).unwrap();
+    let file = &nzb.files()[0];
+
+    assert_eq!(file.expected_segment_count(), None);
+    assert!(!file.is_complete());
+    assert_eq!(file.missing_segments(), vec![2..=u32::MAX - 1]);
+    assert_eq!(file.missing_segment_count(), u64::from(u32::MAX) - 2);
+    assert_eq!(nzb.file_sets()[0].blocks_needed(), Some(u32::MAX));
+}
+
+#[test]
+fn test_file_sets() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    let sets = nzb.file_sets();
+
+    fn names<'a>(files: &[&'a File]) -> Vec<&'a str> {
+        files.iter().filter_map(|f| f.name()).collect()
+    }
+
+    assert_eq!(
+        sets.iter()
+            .map(|set| (set.name(), names(set.files()), names(set.par2_files())))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "Sintel.2010.1080p",
+                vec![
+                    "Sintel.2010.1080p.part01.rar",
+                    "Sintel.2010.1080p.part02.rar",
+                    "Sintel.2010.1080p.part03.rar"
+                ],
+                vec![
+                    "Sintel.2010.1080p.par2",
+                    "Sintel.2010.1080p.vol00+01.par2",
+                    "Sintel.2010.1080p.vol01+02.par2"
+                ]
+            ),
+            (
+                "Sintel.2010.1080p-sample",
+                vec!["Sintel.2010.1080p-sample.rar"],
+                vec!["Sintel.2010.1080p-sample.par2"]
+            ),
+            ("Sintel.2010.1080p.en.srt", vec!["Sintel.2010.1080p.en.srt"], vec![]),
+            ("Sintel.2010.1080p.nfo", vec!["Sintel.2010.1080p.nfo"], vec![]),
+        ]
+    );
+    assert!(sets[0].has_par2());
+    assert!(!sets[2].has_par2());
+    assert_eq!(
+        sets.iter().map(|set| set.size() + set.par2_size()).sum::<u64>(),
+        nzb.size()
+    );
+
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let sets = nzb.file_sets();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0].name(), "Big Buck Bunny - S01E01.mkv");
+    assert_eq!(sets[0].files(), [nzb.file()]);
+    assert_eq!(sets[0].par2_files().len(), 4);
+}
+
+#[test]
+fn test_par2_volumes() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    let sets = nzb.file_sets();
+    let set = &sets[0];
+
+    assert_eq!(
+        set.par2_files().iter().map(|f| f.par2_volume()).collect::<Vec<_>>(),
+        vec![
+            Some(Par2Volume::Index),
+            Some(Par2Volume::Recovery {
+                first_block: 0,
+                block_count: 1
+            }),
+            Some(Par2Volume::Recovery {
+                first_block: 1,
+                block_count: 2
+            }),
+        ]
+    );
+    assert_eq!(set.files()[0].par2_volume(), None);
+    assert_eq!(set.par2_index().and_then(|f| f.name()), Some("Sintel.2010.1080p.par2"));
+    assert_eq!(set.recovery_blocks(), 3);
+    assert_eq!(set.blocks_needed(), Some(0));
+
+    let select = |blocks| {
+        set.select_recovery_volumes(blocks)
+            .map(|files| files.iter().filter_map(|f| f.name()).collect::<Vec<_>>())
+    };
+    assert_eq!(select(0), Some(vec![]));
+    assert_eq!(select(1), Some(vec!["Sintel.2010.1080p.vol00+01.par2"]));
+    assert_eq!(select(2), Some(vec!["Sintel.2010.1080p.vol01+02.par2"]));
+    assert_eq!(
+        select(3),
+        Some(vec![
+            "Sintel.2010.1080p.vol01+02.par2",
+            "Sintel.2010.1080p.vol00+01.par2"
+        ])
+    );
+    assert_eq!(select(4), None);
+
+    // The sample set has an index file, but no recovery volumes.
+    assert_eq!(sets[1].recovery_blocks(), 0);
+    assert_eq!(sets[1].block_size(), None);
+    assert_eq!(sets[1].select_recovery_volumes(1), None);
+
+    let nzb = Nzb::parse_file(get_file("valid_nzb_with_bad_segments.nzb")).unwrap();
+    let set = &nzb.file_sets()[0];
+    assert_eq!(set.recovery_blocks(), 7);
+    assert_eq!(set.block_size(), Some(740291));
+    // Segments 11-13 of the main file are missing, each overlapping at most two blocks.
+    assert_eq!(set.blocks_needed(), Some(6));
+    assert_eq!(
+        set.select_recovery_volumes(6)
+            .unwrap()
+            .iter()
+            .filter_map(|f| f.name())
+            .collect::<Vec<_>>(),
+        vec![
+            "Big Buck Bunny - S01E01.mkv.vol03+04.par2",
+            "Big Buck Bunny - S01E01.mkv.vol01+02.par2"
+        ]
+    );
+}
+
+#[test]
+fn test_rar_volumes() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    let sets = nzb.file_sets();
+
+    let volume = sets[0].files()[1].rar_volume().unwrap();
+    assert_eq!(volume.base(), "Sintel.2010.1080p");
+    assert_eq!(volume.number(), 1);
+    assert!(!volume.is_first());
+    assert!(sets[0].missing_rar_volumes().is_empty());
+    assert_eq!(
+        nzb.rar_files().filter_map(|f| f.name()).collect::<Vec<_>>(),
+        vec![
+            "Sintel.2010.1080p.part01.rar",
+            "Sintel.2010.1080p.part02.rar",
+            "Sintel.2010.1080p.part03.rar",
+            "Sintel.2010.1080p-sample.rar"
+        ]
+    );
+
+    // Only the second volume of `abc-mr2a` is present.
+    let nzb = Nzb::parse_file(get_file("multi_rar.nzb")).unwrap();
+    let sets = nzb.file_sets();
+    assert_eq!(
+        sets.iter().map(|set| set.name()).collect::<Vec<_>>(),
+        vec!["abc-mr2a", "Big Buck Bunny - S01E01"]
+    );
+    assert_eq!(sets[0].missing_rar_volumes(), vec![0, 1]);
+    assert!(sets[1].missing_rar_volumes().is_empty());
+
+    let files = ["movie.r01", "movie.rar", "movie.r03", "movie.r00"].map(|name| {
+        File::new(
+            "poster",
+            DateTime::UNIX_EPOCH,
+            format!("\"{name}\" yEnc (1/1) 1024"),
+            ["alt.binaries.test"],
+            [Segment::new(1024, 1, format!("{name}@example"))],
+        )
+    });
+    let nzb = Nzb::builder().files(files).build().unwrap();
+    let sets = nzb.file_sets();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(
+        sets[0]
+            .rar_volumes()
+            .iter()
+            .filter_map(|f| f.name())
+            .collect::<Vec<_>>(),
+        vec!["movie.rar", "movie.r00", "movie.r01", "movie.r03"]
+    );
+    assert_eq!(sets[0].missing_rar_volumes(), vec![3]);
+
+    // The volume number comes straight from the name, so it must not decide how much memory is used.
+    let files = ["movie.part0001.rar", "movie.part0004.rar", "movie.part4000000000.rar"].map(|name| {
+        File::new(
+            "poster",
+            DateTime::UNIX_EPOCH,
+            format!("\"{name}\" yEnc (1/1) 1024"),
+            ["alt.binaries.test"],
+            [Segment::new(1024, 1, format!("{name}@example"))],
+        )
+    });
+    let nzb = Nzb::builder().files(files).build().unwrap();
+    let missing = nzb.file_sets()[0].missing_rar_volumes();
+    assert_eq!(missing.len(), 10_000);
+    assert_eq!(missing[..4], [1, 2, 4, 5]);
+    assert_eq!(missing.last(), Some(&10_001));
+}
+
+#[test]
+fn test_file_kinds() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+
+    assert_eq!(
+        nzb.files().iter().map(File::kind).collect::<Vec<_>>(),
+        vec![
+            FileKind::Rar,
+            FileKind::Rar,
+            FileKind::Rar,
+            FileKind::Par2,
+            FileKind::Par2,
+            FileKind::Par2,
+            FileKind::Rar,
+            FileKind::Par2,
+            FileKind::Subtitle,
+            FileKind::Nfo,
+        ]
+    );
+    assert_eq!(nzb.files_of_kind(FileKind::Rar).count(), 4);
+    assert_eq!(
+        nzb.files_of_kind(FileKind::Subtitle)
+            .filter_map(|f| f.name())
+            .collect::<Vec<_>>(),
+        vec!["Sintel.2010.1080p.en.srt"]
+    );
+    assert!(nzb.has_kind(FileKind::Nfo));
+    assert!(!nzb.has_kind(FileKind::Video));
+    assert!(!nzb.is_suspicious());
+
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    assert_eq!(nzb.file().kind(), FileKind::Video);
+    assert!(!nzb.is_suspicious());
+
+    let payload = File::new(
+        "poster",
+        DateTime::UNIX_EPOCH,
+        r#"[1/1] - "Big Buck Bunny - S01E01.mkv.exe" yEnc (1/1) 1024"#,
+        ["alt.binaries.test"],
+        [Segment::new(1024, 1, "payload@example")],
+    );
+    let nzb = Nzb::builder().file(payload).build().unwrap();
+    assert_eq!(nzb.file().kind(), FileKind::Executable);
+    assert!(nzb.is_suspicious());
+}
+
+#[test]
+fn test_main_file() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    assert_eq!(nzb.main_set().map(|set| set.name()), Some("Sintel.2010.1080p"));
+    assert_eq!(
+        nzb.main_file().and_then(|f| f.name()),
+        Some("Sintel.2010.1080p.part01.rar")
+    );
+
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    assert_eq!(nzb.main_file(), Some(nzb.file()));
+
+    // The sample is larger than any single volume, but not than the whole archive.
+    let file = |name: &str, size: u32| {
+        File::new(
+            "poster",
+            DateTime::UNIX_EPOCH,
+            format!("\"{name}\" yEnc (1/1) {size}"),
+            ["alt.binaries.test"],
+            [Segment::new(size, 1, format!("{name}@example"))],
+        )
+    };
+    let nzb = Nzb::builder()
+        .files([
+            file("movie.part01.rar", 1000),
+            file("movie.part02.rar", 1000),
+            file("movie.part03.rar", 500),
+            file("movie-sample.mkv", 1200),
+            file("movie.trailer.mkv", 5000),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(nzb.file().name(), Some("movie.trailer.mkv"));
+    assert_eq!(nzb.main_set().map(|set| set.name()), Some("movie"));
+    assert_eq!(nzb.main_file().and_then(|f| f.name()), Some("movie.part01.rar"));
+
+    // Samples are only picked if there is nothing else.
+    let nzb = Nzb::builder()
+        .files([file("movie-sample.mkv", 1200), file("movie-sample.mkv.par2", 100)])
+        .build()
+        .unwrap();
+    assert_eq!(nzb.main_file().and_then(|f| f.name()), Some("movie-sample.mkv"));
+}
+
+#[test]
+fn test_content_files() {
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    assert_eq!(
+        nzb.content_files().filter_map(|f| f.name()).collect::<Vec<_>>(),
+        vec![
+            "Sintel.2010.1080p.part01.rar",
+            "Sintel.2010.1080p.part02.rar",
+            "Sintel.2010.1080p.part03.rar",
+            "Sintel.2010.1080p.en.srt"
+        ]
+    );
+
+    let file = |name: &str| {
+        File::new(
+            "poster",
+            DateTime::UNIX_EPOCH,
+            format!("\"{name}\" yEnc (1/1) 1024"),
+            ["alt.binaries.test"],
+            [Segment::new(1024, 1, format!("{name}@example"))],
+        )
+    };
+    let nzb = Nzb::builder()
+        .files(
+            [
+                "movie.mkv",
+                "sample.mkv",
+                "movie-proof.jpg",
+                "movie.nfo",
+                "movie.sfv",
+                "Subs/English.srt",
+                "movie.featurette.mkv",
+                "movie.mkv.par2",
+            ]
+            .map(file),
+        )
+        .build()
+        .unwrap();
+
+    let flags = |name: &str| {
+        let file = nzb.files().iter().find(|f| f.name() == Some(name)).unwrap();
+        (file.is_sample(), file.is_proof(), file.is_extra())
+    };
+    assert_eq!(flags("movie.mkv"), (false, false, false));
+    assert_eq!(flags("sample.mkv"), (true, false, false));
+    assert_eq!(flags("movie-proof.jpg"), (false, true, false));
+    assert_eq!(flags("movie.nfo"), (false, false, true));
+    assert_eq!(flags("movie.sfv"), (false, false, true));
+    assert_eq!(flags("Subs/English.srt"), (false, false, true));
+    assert_eq!(flags("movie.featurette.mkv"), (false, false, true));
+    assert_eq!(
+        nzb.content_files().filter_map(|f| f.name()).collect::<Vec<_>>(),
+        vec!["movie.mkv"]
+    );
+}
+
+#[cfg(feature = "release")]
+#[test]
+fn test_release_info() {
+    use nzb_rs::{ReleaseCodec, ReleaseSource};
+
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let release = nzb.release_info().unwrap();
+    assert_eq!(release.title(), "Big Buck Bunny");
+    assert_eq!(release.seasons(), [1]);
+    assert_eq!(release.episodes(), [1]);
+    assert!(release.is_episode());
+    assert_eq!(nzb.file().release_info(), Some(release));
+
+    // Without a title, the name of the main set is used.
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    let nzb = Nzb::builder().files(nzb.files().to_vec()).build().unwrap();
+    let release = nzb.release_info().unwrap();
+    assert_eq!(release.title(), "Sintel");
+    assert_eq!(release.year(), Some(2010));
+    assert_eq!(release.resolution(), Some(1080));
+    assert_eq!(release.source(), None);
+    assert_eq!(release.codec(), None);
+    assert!(!release.is_episode());
+
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    assert_eq!(nzb.release_info().unwrap().title(), "Your File!");
+
+    let release = nzb_rs::ReleaseInfo::parse("Sintel.2010.1080p.BluRay.x264-GRP");
+    assert_eq!(release.source(), Some(ReleaseSource::BluRay));
+    assert_eq!(release.codec(), Some(ReleaseCodec::H264));
+    assert_eq!(release.group(), Some("GRP"));
+}
+
+#[rstest]
+#[case::spec_example(get_file("spec_example.nzb.gz"), Some("Your File!"))]
+#[case::big_buck_bunny(get_file("big_buck_bunny.nzb"), Some("Big Buck Bunny - S01E01"))]
+#[case::multiple_sets(get_file("multiple_sets.nzb"), Some("Sintel"))]
+#[case::no_meta(get_file("no_meta.nzb"), Some("Here's your file!"))]
+#[case::bad_subject(get_file("bad_subject.nzb"), Some("bad_subject"))]
+fn test_display_name(#[case] nzb_file: PathBuf, #[case] expected: Option<&str>) {
+    let nzb = Nzb::parse_file(nzb_file).unwrap();
+    assert_eq!(nzb.display_name(), expected);
+}
+
+#[test]
+fn test_display_name_fallbacks() {
+    let nzb_with_subjects = |subjects: &[&str]| {
+        let files = subjects
+            .iter()
+            .enumerate()
+            .map(|(index, subject)| {
+                format!(
+                    r#"<file poster="John" date="1706440708" subject="{subject}">
+                        <groups><group>alt.binaries.boneless</group></groups>
+                        <segments><segment bytes="100" number="1">{index}@example</segment></segments>
+                    </file>"#
+                )
+            })
+            .collect::<String>();
+        Nzb::parse(format!(
+            r#"<nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">{files}</nzb>"#
+        ))
+        .unwrap()
+    };
+
+    // The most common clear-text set name wins over obfuscated files.
+    let nzb = nzb_with_subjects(&[
+        r#"[1/4] - &quot;b082fa0beaa644d3aa01045d5b8d0b36.mkv&quot; yEnc (1/1) 100"#,
+        r#"[2/4] - &quot;Sintel.2010.1080p.par2&quot; yEnc (1/1) 100"#,
+        r#"[3/4] - &quot;Sintel.2010.1080p.vol00+01.par2&quot; yEnc (1/1) 100"#,
+        r#"[4/4] - &quot;Sintel.2010.1080p.en.srt&quot; yEnc (1/1) 100"#,
+    ]);
+    assert!(nzb.is_obfuscated());
+    assert_eq!(nzb.display_name(), Some("Sintel.2010.1080p"));
+
+    // An obfuscated prefix is skipped.
+    let nzb = nzb_with_subjects(&[
+        r#"b082fa0beaa644d3aa01045d5b8d0b36 [1/1] - &quot;Sintel.2010.1080p.mkv&quot; yEnc (1/1) 100"#,
+    ]);
+    assert_eq!(nzb.display_name(), Some("Sintel.2010.1080p"));
+
+    // Nothing to go by.
+    let nzb = nzb_with_subjects(&[r#"[1/1] - &quot;b082fa0beaa644d3aa01045d5b8d0b36.mkv&quot; yEnc (1/1) 100"#]);
+    assert_eq!(nzb.display_name(), None);
+    assert_eq!(nzb.source_name(), None);
+}
+
+#[test]
+fn test_source_name() {
+    let file = get_file("spec_example.nzb.gz");
+    let nzb = Nzb::parse_file(&file).unwrap();
+    assert_eq!(nzb.source_name(), Some("spec_example.nzb.gz"));
+
+    // The source is not part of the NZB itself.
+    let from_bytes = Nzb::from_bytes(fs::read(&file).unwrap()).unwrap();
+    assert_eq!(from_bytes.source_name(), None);
+    assert_eq!(from_bytes, nzb);
+
+    // Editing an NZB keeps its source.
+    let mut builder = NzbBuilder::from(nzb.clone());
+    builder.meta_mut().remove("password");
+    let edited = builder.build().unwrap();
+    assert_eq!(edited.source_name(), Some("spec_example.nzb.gz"));
+    assert_eq!(edited.display_name(), nzb.display_name());
+
+    // Merging keeps the source of the first NZB.
+    let (merged, _) = Nzb::merge([nzb.clone(), from_bytes.clone()]).unwrap();
+    assert_eq!(merged.source_name(), Some("spec_example.nzb.gz"));
+    let (merged, _) = Nzb::merge([from_bytes, nzb]).unwrap();
+    assert_eq!(merged.source_name(), None);
+}
+
+#[test]
+fn test_parse_file_with_passwords() {
+    let dir = PathBuf::new().join(env!("CARGO_TARGET_TMPDIR")).join("test_parser");
+    fs::create_dir_all(&dir).unwrap();
+
+    // The spec example already has the password `secret`.
+    let path = dir.join("Sintel{{hunter2}}.nzb.gz");
+    fs::copy(get_file("spec_example.nzb.gz"), &path).unwrap();
+    let (nzb, name) = Nzb::parse_file_with_passwords(&path).unwrap();
+    assert_eq!(name, "Sintel");
+    assert_eq!(nzb.meta().title(), Some("Your File!"));
+    assert_eq!(nzb.meta().passwords(), vec!["secret", "hunter2"]);
+    assert_eq!(nzb.source_name(), Some("Sintel{{hunter2}}.nzb.gz"));
+
+    let path = dir.join("Sintel.nzb");
+    let original = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let xml = original.to_xml().replace(">Your File!<", ">Your File! / secret<");
+    fs::write(&path, xml).unwrap();
+    let (nzb, name) = Nzb::parse_file_with_passwords(&path).unwrap();
+    assert_eq!(name, "Sintel");
+    assert_eq!(nzb.meta().title(), Some("Your File!"));
+    assert_eq!(nzb.meta().passwords(), vec!["secret"]);
+    assert_eq!(nzb, original);
+}
+
+#[test]
+fn test_display_name_without_password() {
+    let dir = PathBuf::new().join(env!("CARGO_TARGET_TMPDIR")).join("test_parser");
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("bad_subject{{secret}}.nzb");
+    fs::copy(get_file("bad_subject.nzb"), &path).unwrap();
+    let nzb = Nzb::parse_file(&path).unwrap();
+    assert_eq!(nzb.display_name(), Some("bad_subject"));
+}
+
+#[test]
+fn test_merge() {
+    let original = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let mkv = original.file();
+
+    // A repost that only has the first half of the segments, in another group.
+    let partial = File::new(
+        mkv.poster(),
+        *mkv.posted_at(),
+        mkv.subject(),
+        ["alt.binaries.test"],
+        mkv.segments()[..12].to_vec(),
+    );
+    let mut repost = NzbBuilder::new().file(partial.clone());
+    repost.meta_mut().append("password", "secret");
+    let repost = repost.build().unwrap();
+
+    let (merged, report) = Nzb::merge([repost, original.clone()]).unwrap();
+    assert_eq!(report.nzbs(), 2);
+    assert!(report.duplicate_files().is_empty());
+    assert_eq!(report.duplicate_segments(), 12);
+    assert!(report.has_duplicates());
+    assert_eq!(merged.files().len(), original.files().len());
+    assert_eq!(merged.file().segments(), mkv.segments());
+    assert_eq!(merged.file().groups(), ["alt.binaries.boneless", "alt.binaries.test"]);
+    assert_eq!(merged.meta().passwords(), vec!["secret"]);
+    assert!(merged.is_complete());
+
+    // A repost of the other half, under a subject with a poster tag and a different counter.
+    let subject = mkv
+        .subject()
+        .replace("[1/5]", "[REPOST] [1/5]")
+        .replace("(1/24)", "(1/12)");
+    let renamed = File::new(
+        mkv.poster(),
+        *mkv.posted_at(),
+        subject,
+        ["alt.binaries.test"],
+        mkv.segments()[12..].to_vec(),
+    );
+    let first_half = NzbBuilder::new().file(partial.clone()).build().unwrap();
+    let second_half = NzbBuilder::new().file(renamed).build().unwrap();
+    let (merged, report) = Nzb::merge([first_half, second_half]).unwrap();
+    assert!(!report.has_duplicates());
+    assert_eq!(merged.files().len(), 1);
+    assert_eq!(merged.file().subject(), mkv.subject());
+    assert_eq!(merged.file().segments(), mkv.segments());
+    assert!(merged.is_complete());
+
+    // Merging a different release keeps the files of both, and the first title.
+    let spec = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let other = NzbBuilder::from(spec.clone())
+        .meta(Meta::new(
+            Some("Other"),
+            ["secret", "other"],
+            ["HD", "x265"],
+            Some("Movies"),
+        ))
+        .build()
+        .unwrap();
+    let (merged, report) = Nzb::merge([original.clone(), spec.clone(), other]).unwrap();
+    assert_eq!(report.nzbs(), 3);
+    assert_eq!(
+        report.duplicate_files(),
+        spec.files().iter().map(File::subject).collect::<Vec<_>>()
+    );
+    assert_eq!(report.duplicate_segments(), 0);
+    assert_eq!(merged.files().len(), original.files().len() + spec.files().len());
+    assert_eq!(merged.meta().title(), Some("Your File!"));
+    assert_eq!(merged.meta().category(), Some("TV"));
+    assert_eq!(merged.meta().passwords(), vec!["secret", "other"]);
+    assert_eq!(merged.meta().tags(), vec!["HD", "x265"]);
+
+    // Merging an NZB with itself changes nothing.
+    let (merged, report) = Nzb::merge([original.clone()]).unwrap();
+    assert_eq!(merged, original);
+    assert!(!report.has_duplicates());
+
+    assert_eq!(Nzb::merge([]), Err(ParseNzbError::FileElement));
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use flate2::read::GzDecoder;
+use nzb_rs::{File, FileAttributeKind, Nzb, NzbReader, ParseNzbError, TextPosition};
+use rstest::rstest;
+
+fn get_file(name: &str) -> PathBuf {
+    PathBuf::new()
+        .join(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("nzbs")
+        .join(name)
+}
+
+fn read_all(xml: &str) -> Result<Vec<File>, ParseNzbError> {
+    NzbReader::new(xml.as_bytes())?.collect()
+}
+
+#[rstest]
+#[case::spec_example(get_file("spec_example.nzb"))]
+#[case::big_buck_bunny(get_file("big_buck_bunny.nzb"))]
+#[case::valid_nzb_with_bad_segments(get_file("valid_nzb_with_bad_segments.nzb"))]
+#[case::bad_subject(get_file("bad_subject.nzb"))]
+#[case::multi_rar(get_file("multi_rar.nzb"))]
+#[case::no_meta(get_file("no_meta.nzb"))]
+#[case::single_meta(get_file("single_meta.nzb"))]
+#[case::non_standard_meta(get_file("non_standard_meta.nzb"))]
+#[case::nzb_with_no_head(get_file("nzb_with_no_head.nzb"))]
+fn test_reader_matches_parser(#[case] nzb_file: PathBuf) {
+    let nzb = Nzb::parse_file(&nzb_file).unwrap();
+
+    let mut reader = NzbReader::new(BufReader::new(fs::File::open(&nzb_file).unwrap())).unwrap();
+    assert_eq!(reader.meta(), nzb.meta());
+
+    let mut files = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(reader.next().is_none());
+
+    let mut expected = nzb.files().to_vec();
+    files.sort();
+    expected.sort();
+    assert_eq!(files, expected);
+}
+
+#[test]
+fn test_reader_gzipped() {
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb.gz")).unwrap();
+    let decoder = GzDecoder::new(fs::File::open(get_file("big_buck_bunny.nzb.gz")).unwrap());
+    let mut files = NzbReader::new(BufReader::new(decoder))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    files.sort();
+    let mut expected = nzb.files().to_vec();
+    expected.sort();
+    assert_eq!(files, expected);
+}
+
+#[rstest]
+#[case::malformed_files("malformed_files.nzb")]
+#[case::malformed_files2("malformed_files2.nzb")]
+#[case::malformed_groups("malformed_groups.nzb")]
+#[case::malformed_segments("malformed_segments.nzb")]
+fn test_reader_errors(#[case] name: &str) {
+    let xml = fs::read_to_string(get_file(name)).unwrap();
+
+    // The reader reports the same error as the parser.
+    match (read_all(&xml).unwrap_err(), Nzb::parse(&xml).unwrap_err()) {
+        (ParseNzbError::FileElement, ParseNzbError::FileElement) => {}
+        (ParseNzbError::GroupsElement { location }, ParseNzbError::GroupsElement { location: expected })
+        | (ParseNzbError::SegmentsElement { location }, ParseNzbError::SegmentsElement { location: expected }) => {
+            assert_eq!(location.index(), expected.index());
+            assert_eq!(location.subject(), expected.subject());
+            assert!(expected.position().is_some());
+            assert_eq!(location.position(), expected.position());
+        }
+        (error, expected) => panic!("{error:?} != {expected:?}"),
+    }
+}
+
+#[test]
+fn test_reader_stops_after_error() {
+    let xml = r#"
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <file poster="Joe" date="1071674882" subject="abc-mr2a.r01 (1/2)">
+            <groups><group>alt.binaries.mojo</group></groups>
+            <segments><segment bytes="102394" number="1">123456789abcdef@news.newzbin.com</segment></segments>
+        </file>
+        <file date="1071674882" subject="abc-mr2a.r02 (1/2)">
+            <groups><group>alt.binaries.mojo</group></groups>
+            <segments><segment bytes="102394" number="1">223456789abcdef@news.newzbin.com</segment></segments>
+        </file>
+        <file poster="Joe" date="1071674882" subject="abc-mr2a.r03 (1/2)">
+            <groups><group>alt.binaries.mojo</group></groups>
+            <segments><segment bytes="102394" number="1">323456789abcdef@news.newzbin.com</segment></segments>
+        </file>
+    </nzb>
+    "#;
+
+    let mut reader = NzbReader::new(xml.as_bytes()).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    match reader.next().unwrap().unwrap_err() {
+        ParseNzbError::FileAttribute { kind, location } => {
+            assert_eq!(kind, FileAttributeKind::Poster);
+            assert_eq!(location.index(), Some(1));
+            assert_eq!(location.subject(), Some("abc-mr2a.r02 (1/2)"));
+        }
+        _ => panic!(),
+    }
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_reader_only_par2_files() {
+    let xml = r#"
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <file poster="Joe" date="1590927494" subject="[1/1] - &quot;Foobar.par2&quot; yEnc (1/1) 388">
+            <groups><group>alt.binaries.boneless</group></groups>
+            <segments><segment bytes="581" number="1">MtUwAvUsIaGzDhHhJgXsXaFv-1690927494721@nyuu</segment></segments>
+        </file>
+    </nzb>
+    "#;
+
+    let mut reader = NzbReader::new(xml.as_bytes()).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!(reader.next().unwrap().unwrap_err(), ParseNzbError::OnlyPar2Files);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_reader_unescapes_text_and_attributes() {
+    let xml = r#"
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <head><meta type="title">Tom &amp; Jerry&#39;s</meta></head>
+        <file poster="Joe &lt;joe@example&gt;" date="1071674882" subject="&quot;a.mkv&quot; yEnc (1/1)">
+            <groups><group>alt.binaries.a&amp;b</group></groups>
+            <segments><segment bytes="10" number="1">&lt;abc@example&gt;</segment></segments>
+        </file>
+    </nzb>
+    "#;
+
+    let mut reader = NzbReader::new(xml.as_bytes()).unwrap();
+    assert_eq!(reader.meta().title(), Some("Tom & Jerry's"));
+
+    let file = reader.next().unwrap().unwrap();
+    assert_eq!(file.poster(), "Joe <joe@example>");
+    assert_eq!(file.subject(), r#""a.mkv" yEnc (1/1)"#);
+    assert_eq!(file.groups(), ["alt.binaries.a&b"]);
+    assert_eq!(file.segments()[0].message_id(), "<abc@example>");
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_reader_invalid_xml() {
+    let xml = r#"
+    <nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+        <file poster="Joe" date="1071674882" subject="abc-mr2a.r01 (1/2)">
+            <groups><group>alt.binaries.mojo</group></groups>
+    "#;
+
+    assert!(matches!(
+        read_all(xml),
+        Err(ParseNzbError::XmlSyntax { position: None, .. })
+    ));
+
+    // Syntax errors are reported at the start of the offending markup, like the parser does.
+    let xml = "<nzb>\n  <head>\n    <meta type=\"title\">Tom &amp; Jérôme</meta>\n  </nzb>";
+    assert!(matches!(
+        NzbReader::new(xml.as_bytes()),
+        Err(ParseNzbError::XmlSyntax {
+            position: Some(TextPosition { line: 4, column: 3 }),
+            ..
+        })
+    ));
+    assert!(matches!(
+        Nzb::parse(xml),
+        Err(ParseNzbError::XmlSyntax {
+            position: Some(TextPosition { line: 4, column: 3 }),
+            ..
+        })
+    ));
+
+    // Columns are counted in characters.
+    let xml = "<nzb><head><meta type=\"title\">Jérôme &bogus;</meta></head></nzb>";
+    assert!(matches!(
+        NzbReader::new(xml.as_bytes()),
+        Err(ParseNzbError::XmlSyntax {
+            position: Some(TextPosition { line: 1, column: 38 }),
+            ..
+        })
+    ));
+}
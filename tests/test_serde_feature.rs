@@ -18,8 +18,9 @@ fn get_file(name: &str) -> PathBuf {
 fn test_serde_feature(#[case] nzb_file: PathBuf) {
     let original = Nzb::parse_file(nzb_file).unwrap();
     let serialized = serde_json::to_string(&original).unwrap();
-    let nzb = serde_json::from_str(&serialized).unwrap();
+    let nzb: Nzb = serde_json::from_str(&serialized).unwrap();
     assert_eq!(original, nzb);
+    assert_eq!(original.source_name(), nzb.source_name());
 }
 
 #[rstest]
@@ -31,3 +32,33 @@ fn test_valid_nzb_with_one_missing_segment(#[case] nzb_file: PathBuf) {
     let nzb = serde_json::from_str(&serialized).unwrap();
     assert_eq!(original, nzb);
 }
+
+#[test]
+fn test_main_file_of_deserialized_par2_only_nzb() {
+    let original = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let mut value = serde_json::to_value(&original).unwrap();
+    value["files"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|file| file["subject"].as_str().unwrap().contains(".par2"));
+    let nzb: Nzb = serde_json::from_value(value).unwrap();
+
+    assert_eq!(nzb.files().len(), 4);
+    assert!(nzb.main_set().is_none());
+    assert!(nzb.main_file().is_none());
+}
+
+#[test]
+fn test_meta_without_entries() {
+    // Metadata serialized before every entry was kept has no `entries`.
+    let original = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let mut value = serde_json::to_value(&original).unwrap();
+    value["meta"].as_object_mut().unwrap().remove("entries");
+    let nzb: Nzb = serde_json::from_value(value).unwrap();
+
+    assert_eq!(nzb, original);
+    assert_eq!(nzb.meta().title(), Some("Your File!"));
+    assert_eq!(nzb.meta().passwords(), ["secret"]);
+    assert_eq!(nzb.meta().get("tag"), Some("HD"));
+    assert_eq!(nzb.meta().iter().count(), 4);
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use flate2::read::GzDecoder;
+use nzb_rs::{File, Nzb, NzbBuilder, ParseNzbError, Segment, WriteNzbFileError};
+use rstest::rstest;
+
+fn get_file(name: &str) -> PathBuf {
+    PathBuf::new()
+        .join(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("nzbs")
+        .join(name)
+}
+
+#[rstest]
+#[case::spec_example_nzb(get_file("spec_example.nzb"))]
+#[case::big_buck_bunny_nzb(get_file("big_buck_bunny.nzb"))]
+#[case::valid_nzb_with_bad_segments(get_file("valid_nzb_with_bad_segments.nzb"))]
+#[case::multi_rar(get_file("multi_rar.nzb"))]
+#[case::no_meta(get_file("no_meta.nzb"))]
+#[case::non_standard_meta(get_file("non_standard_meta.nzb"))]
+fn test_roundtrip(#[case] nzb_file: PathBuf) {
+    let original = Nzb::parse_file(nzb_file).unwrap();
+    let xml = original.to_xml();
+    let nzb = Nzb::parse(&xml).unwrap();
+    assert_eq!(original, nzb);
+}
+
+#[test]
+fn test_write_to_matches_to_xml() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let mut out = Vec::new();
+    nzb.write_to(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), nzb.to_xml());
+}
+
+#[test]
+fn test_spec_example_output() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE nzb PUBLIC "-//newzBin//DTD NZB 1.1//EN" "http://www.newzbin.com/DTD/nzb/nzb-1.1.dtd">
+<nzb xmlns="http://www.newzbin.com/DTD/2003/nzb">
+    <head>
+        <meta type="title">Your File!</meta>
+        <meta type="password">secret</meta>
+        <meta type="tag">HD</meta>
+        <meta type="category">TV</meta>
+    </head>
+    <file poster="Joe Bloggs &lt;bloggs@nowhere.example&gt;" date="1071674882" subject="Here&apos;s your file!  abc-mr2a.r01 (1/2)">
+        <groups>
+            <group>alt.binaries.mojo</group>
+            <group>alt.binaries.newzbin</group>
+        </groups>
+        <segments>
+            <segment bytes="102394" number="1">123456789abcdef@news.newzbin.com</segment>
+            <segment bytes="4501" number="2">987654321fedbca@news.newzbin.com</segment>
+        </segments>
+    </file>
+</nzb>
+"#;
+    assert_eq!(nzb.to_xml(), expected);
+}
+
+#[test]
+fn test_escaped_attributes_roundtrip() {
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let xml = nzb.to_xml();
+
+    assert!(xml.contains(r#"poster="John &lt;nzb@nowhere.example&gt;""#));
+    assert!(xml.contains(r#"subject="[1/5] - &quot;Big Buck Bunny - S01E01.mkv&quot; yEnc (1/24) 16981056""#));
+
+    let parsed = Nzb::parse(xml).unwrap();
+    let file = &parsed.files()[0];
+    assert_eq!(file.poster(), "John <nzb@nowhere.example>");
+    assert_eq!(
+        file.subject(),
+        r#"[1/5] - "Big Buck Bunny - S01E01.mkv" yEnc (1/24) 16981056"#
+    );
+    assert_eq!(file.posted_at(), &DateTime::from_timestamp(1706440708, 0).unwrap());
+    assert_eq!(
+        file.segments()[0],
+        Segment::new(739067, 1, "9cacde4c986547369becbf97003fb2c5-9483514693959@example")
+    );
+    assert_eq!(file, &nzb.files()[0]);
+}
+
+#[test]
+fn test_edited_roundtrip() {
+    // Strip the passwords.
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let mut builder = NzbBuilder::from(nzb);
+    builder.meta_mut().remove("password");
+    let edited = builder.build().unwrap();
+    assert!(edited.meta().passwords().is_empty());
+    assert_eq!(Nzb::parse(edited.to_xml()).unwrap(), edited);
+
+    // Keep only the main files, and rename the first one.
+    let nzb = Nzb::parse_file(get_file("multiple_sets.nzb")).unwrap();
+    let mut builder = NzbBuilder::from(nzb).retain_files(|file| file.is_rar() && !file.is_sample());
+    builder.files_mut()[0].set_subject(r#"[01/10] - "Sintel.part01.rar" yEnc (1/3) 2000000"#);
+    let edited = builder.build().unwrap();
+    assert_eq!(
+        edited.files().iter().filter_map(File::name).collect::<Vec<_>>(),
+        vec![
+            "Sintel.part01.rar",
+            "Sintel.2010.1080p.part02.rar",
+            "Sintel.2010.1080p.part03.rar"
+        ]
+    );
+    assert_eq!(Nzb::parse(edited.to_xml()).unwrap(), edited);
+}
+
+#[test]
+fn test_edited_blank_groups() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+
+    // Blank groups cannot be read back, so they are discarded when building.
+    let mut builder = NzbBuilder::from(nzb.clone());
+    builder.files_mut()[0].set_groups(["", "alt.binaries.mojo", " \t"]);
+    let edited = builder.build().unwrap();
+    assert_eq!(edited.file().groups(), ["alt.binaries.mojo"]);
+    assert_eq!(Nzb::parse(edited.to_xml()).unwrap(), edited);
+
+    // Without any group left, the edit is rejected the way the parser would reject the output.
+    let mut builder = NzbBuilder::from(nzb);
+    builder.files_mut()[0].set_groups([""]);
+    match builder.build().unwrap_err() {
+        ParseNzbError::GroupsElement { location } => {
+            assert_eq!(location.index(), Some(0));
+            assert_eq!(location.subject(), Some("Here's your file!  abc-mr2a.r01 (1/2)"));
+        }
+        error => panic!("{error:?}"),
+    }
+}
+
+#[test]
+fn test_whitespace_control_characters_roundtrip() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let mut builder = NzbBuilder::from(nzb);
+    builder.meta_mut().set("title", "Your File!\r\nPart\t1");
+    builder.files_mut()[0].set_subject("Here's\tyour file!\n abc-mr2a.r01 (1/2)\r");
+    let edited = builder.build().unwrap();
+
+    let xml = edited.to_xml();
+    assert!(xml.contains("Your File!&#13;&#10;Part&#9;1"));
+    assert!(xml.contains("Here&apos;s&#9;your file!&#10; abc-mr2a.r01 (1/2)&#13;"));
+    assert_eq!(Nzb::parse(xml).unwrap(), edited);
+}
+
+#[test]
+fn test_forbidden_characters_are_dropped() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let mut builder = NzbBuilder::from(nzb);
+    builder.meta_mut().set("title", "Your\u{1} File!");
+    builder.files_mut()[0].set_subject("Here's your file!\u{0}\u{1b}\u{FFFF}  abc-mr2a.r01 (1/2)");
+    let edited = builder.build().unwrap();
+
+    let parsed = Nzb::parse(edited.to_xml()).unwrap();
+    assert_eq!(parsed.meta().title(), Some("Your File!"));
+    assert_eq!(parsed.file().subject(), "Here's your file!  abc-mr2a.r01 (1/2)");
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = PathBuf::new().join(env!("CARGO_TARGET_TMPDIR")).join("test_writer");
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+#[rstest]
+#[case::nzb("spec_example.nzb", false)]
+#[case::nzb_gz("spec_example.nzb.gz", true)]
+#[case::nzb_gz_uppercase("spec_example.NZB.GZ", true)]
+fn test_write_file(#[case] name: &str, #[case] gzipped: bool) {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let path = temp_file(name);
+    nzb.write_file(&path).unwrap();
+
+    let bytes = fs::read(&path).unwrap();
+    assert_eq!(bytes.starts_with(&[0x1f, 0x8b]), gzipped);
+
+    let content = if gzipped {
+        let mut content = String::new();
+        GzDecoder::new(&bytes[..]).read_to_string(&mut content).unwrap();
+        content
+    } else {
+        String::from_utf8(bytes).unwrap()
+    };
+    assert_eq!(content, nzb.to_xml());
+    assert_eq!(Nzb::parse_file(&path).unwrap(), nzb);
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+#[case(9)]
+#[case(42)]
+fn test_write_file_with_level(#[case] level: u32) {
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let path = temp_file(&format!("big_buck_bunny_{level}.nzb.gz"));
+    nzb.write_file_with_level(&path, level).unwrap();
+    assert_eq!(Nzb::parse_file(&path).unwrap(), nzb);
+}
+
+#[test]
+fn test_write_file_compression_levels_differ() {
+    let nzb = Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap();
+    let stored = temp_file("levels_stored.nzb.gz");
+    let best = temp_file("levels_best.nzb.gz");
+    nzb.write_file_with_level(&stored, 0).unwrap();
+    nzb.write_file_with_level(&best, 9).unwrap();
+    assert!(fs::metadata(&best).unwrap().len() < fs::metadata(&stored).unwrap().len());
+}
+
+#[test]
+fn test_write_file_into_missing_directory() {
+    let nzb = Nzb::parse_file(get_file("spec_example.nzb")).unwrap();
+    let path = temp_file("i dont exist").join("spec_example.nzb");
+
+    match nzb.write_file(&path).unwrap_err() {
+        WriteNzbFileError::Io { source, file } => {
+            assert_eq!(source.kind(), io::ErrorKind::NotFound);
+            assert_eq!(file, path);
+        }
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "zip")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use nzb_rs::{Nzb, ParseNzbError, ParseNzbFileError, ParseNzbReaderError};
+
+fn get_file(name: &str) -> PathBuf {
+    PathBuf::new()
+        .join(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("nzbs")
+        .join(name)
+}
+
+#[test]
+fn test_parse_zip_file() {
+    let nzbs = Nzb::parse_zip_file(get_file("archive.zip")).unwrap();
+
+    // The readme and the empty directory are skipped.
+    assert_eq!(
+        nzbs.keys().collect::<Vec<_>>(),
+        vec!["big_buck_bunny.nzb", "spec_example.nzb.gz"]
+    );
+    assert_eq!(
+        nzbs["big_buck_bunny.nzb"],
+        Nzb::parse_file(get_file("big_buck_bunny.nzb")).unwrap()
+    );
+    assert_eq!(
+        nzbs["spec_example.nzb.gz"],
+        Nzb::parse_file(get_file("spec_example.nzb")).unwrap()
+    );
+    assert_eq!(nzbs["spec_example.nzb.gz"].source_name(), Some("spec_example.nzb.gz"));
+}
+
+#[test]
+fn test_from_zip_reader() {
+    let bytes = fs::read(get_file("archive.zip")).unwrap();
+    let nzbs = Nzb::from_zip_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(nzbs, Nzb::parse_zip_file(get_file("archive.zip")).unwrap());
+}
+
+#[test]
+fn test_not_a_zip_archive() {
+    match Nzb::parse_zip_file(get_file("spec_example.nzb")).unwrap_err() {
+        ParseNzbFileError::Zip { file, .. } => assert_eq!(file, get_file("spec_example.nzb")),
+        _ => panic!(),
+    }
+
+    let bytes = fs::read(get_file("spec_example.nzb")).unwrap();
+    assert!(matches!(
+        Nzb::from_zip_reader(std::io::Cursor::new(bytes)).unwrap_err(),
+        ParseNzbReaderError::Zip { .. }
+    ));
+}
+
+#[test]
+fn test_invalid_zip_entry() {
+    let file = get_file("archive_with_invalid_nzb.zip");
+
+    match Nzb::parse_zip_file(&file).unwrap_err() {
+        ParseNzbFileError::ZipEntry {
+            source: ParseNzbReaderError::Parse { source },
+            entry,
+            file: path,
+        } => {
+            assert!(matches!(source, ParseNzbError::SegmentsElement { .. }));
+            assert_eq!(entry, "malformed_segments.nzb");
+            assert_eq!(path, file);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_missing_zip_file() {
+    let file = Path::new("non/existent/archive.zip");
+
+    match Nzb::parse_zip_file(file).unwrap_err() {
+        ParseNzbFileError::Io { file: path, .. } => assert_eq!(path, file),
+        _ => panic!(),
+    }
+}